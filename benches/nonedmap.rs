@@ -0,0 +1,127 @@
+#[macro_use]
+extern crate bencher;
+extern crate optional;
+
+use optional::map::NonedHashMap;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// counts the bytes currently allocated, to compare the maps' memory use
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const N: u32 = 10_000;
+
+fn keys() -> impl Iterator<Item = u32> {
+    (0..N).map(|i| i.wrapping_mul(0x9e37_79b9))
+}
+
+fn noned_map() -> NonedHashMap<u32, u32> {
+    let mut map = NonedHashMap::new();
+    for k in keys() {
+        map.insert(k, k).unwrap();
+    }
+    map
+}
+
+fn std_map() -> HashMap<u32, u32> {
+    let mut map = HashMap::new();
+    for k in keys() {
+        map.insert(k, k);
+    }
+    map
+}
+
+fn bench_insert_noned_u32(bench: &mut bencher::Bencher) {
+    bench.iter(|| bencher::black_box(noned_map()));
+}
+
+fn bench_insert_std_u32(bench: &mut bencher::Bencher) {
+    bench.iter(|| bencher::black_box(std_map()));
+}
+
+fn bench_get_noned_u32(bench: &mut bencher::Bencher) {
+    let map = noned_map();
+    bench.iter(|| {
+        for k in keys() {
+            bencher::black_box(map.get(&k));
+        }
+    });
+}
+
+fn bench_get_std_u32(bench: &mut bencher::Bencher) {
+    let map = std_map();
+    bench.iter(|| {
+        for k in keys() {
+            bencher::black_box(map.get(&k));
+        }
+    });
+}
+
+fn bench_remove_noned_u32(bench: &mut bencher::Bencher) {
+    bench.iter(|| {
+        let mut map = noned_map();
+        for k in keys() {
+            bencher::black_box(map.remove(&k));
+        }
+    });
+}
+
+fn bench_remove_std_u32(bench: &mut bencher::Bencher) {
+    bench.iter(|| {
+        let mut map = std_map();
+        for k in keys() {
+            bencher::black_box(map.remove(&k));
+        }
+    });
+}
+
+fn memory_of<M, F: FnOnce() -> M>(build: F) -> usize {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let map = build();
+    let used = ALLOCATED.load(Ordering::Relaxed) - before;
+    drop(map);
+    used
+}
+
+benchmark_group!(
+    bench,
+    bench_insert_noned_u32,
+    bench_insert_std_u32,
+    bench_get_noned_u32,
+    bench_get_std_u32,
+    bench_remove_noned_u32,
+    bench_remove_std_u32
+);
+
+fn main() {
+    println!(
+        "memory for {} u32 -> u32 entries: NonedHashMap {} bytes, HashMap {} bytes",
+        N,
+        memory_of(noned_map),
+        memory_of(std_map)
+    );
+
+    let mut test_opts = bencher::TestOpts::default();
+    if let Some(arg) = std::env::args().skip(1).find(|arg| *arg != "--bench") {
+        test_opts.filter = Some(arg);
+    }
+    bencher::run_tests_console(&test_opts, bench()).unwrap();
+}
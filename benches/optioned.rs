@@ -882,6 +882,96 @@ fn bench_map_or_opt_f64(bench: &mut bencher::Bencher) {
     bench.iter(map_or_opt_f64);
 }
 
+fn column_u32() -> Vec<optional::Optioned<u32>> {
+    [optional::some(1u32), optional::none(), optional::some(3)]
+        .iter()
+        .cycle()
+        .take(1200)
+        .cloned()
+        .collect()
+}
+
+fn bench_map_somes_loop_u32(bench: &mut bencher::Bencher) {
+    let mut column = column_u32();
+    bench.iter(|| {
+        for o in column.iter_mut() {
+            *o = o.map_t(|i| i / 2);
+        }
+        bencher::black_box(&mut column);
+    });
+}
+
+fn bench_map_somes_slice_u32(bench: &mut bencher::Bencher) {
+    let mut column = column_u32();
+    bench.iter(|| {
+        optional::slice::map_somes_in_place(&mut column, |i| i / 2);
+        bencher::black_box(&mut column);
+    });
+}
+
+fn bench_coalesce_loop_u32(bench: &mut bencher::Bencher) {
+    let column = column_u32();
+    let other: Vec<_> = column.iter().rev().cloned().collect();
+    bench.iter(|| {
+        let mut dst = column.clone();
+        for (d, s) in dst.iter_mut().zip(&other) {
+            *d = d.or(*s);
+        }
+        bencher::black_box(dst);
+    });
+}
+
+fn bench_coalesce_slice_u32(bench: &mut bencher::Bencher) {
+    let column = column_u32();
+    let other: Vec<_> = column.iter().rev().cloned().collect();
+    bench.iter(|| {
+        let mut dst = column.clone();
+        optional::slice::coalesce(&mut dst, &other);
+        bencher::black_box(dst);
+    });
+}
+
+fn bench_replace_none_loop_f64(bench: &mut bencher::Bencher) {
+    let column: Vec<_> = column_u32().iter().map(|o| o.map_t(|i| i as f64)).collect();
+    bench.iter(|| {
+        let mut dst = column.clone();
+        for o in dst.iter_mut() {
+            *o = optional::some(o.unwrap_or(0.0));
+        }
+        bencher::black_box(dst);
+    });
+}
+
+fn bench_replace_none_slice_f64(bench: &mut bencher::Bencher) {
+    let column: Vec<_> = column_u32().iter().map(|o| o.map_t(|i| i as f64)).collect();
+    bench.iter(|| {
+        let mut dst = column.clone();
+        optional::slice::replace_none(&mut dst, 0.0);
+        bencher::black_box(dst);
+    });
+}
+
+fn bench_zip_with_loop_u32(bench: &mut bencher::Bencher) {
+    let column = column_u32();
+    let other: Vec<_> = column.iter().rev().cloned().collect();
+    bench.iter(|| {
+        let sum: Vec<optional::Optioned<u32>> = column
+            .iter()
+            .zip(&other)
+            .map(|(a, b)| a.and_then(|x| b.map_t(|y| x + y)))
+            .collect();
+        bencher::black_box(sum);
+    });
+}
+
+fn bench_zip_with_slice_u32(bench: &mut bencher::Bencher) {
+    let column = column_u32();
+    let other: Vec<_> = column.iter().rev().cloned().collect();
+    bench.iter(|| {
+        bencher::black_box(optional::slice::zip_with(&column, &other, |x, y| x + y));
+    });
+}
+
 benchmark_group!(
     bench,
     bench_iter_opt_u8,
@@ -949,7 +1039,15 @@ benchmark_group!(
     bench_map_or_opt_u32,
     bench_map_or_opt_u64,
     bench_map_or_opt_f32,
-    bench_map_or_opt_f64
+    bench_map_or_opt_f64,
+    bench_map_somes_loop_u32,
+    bench_map_somes_slice_u32,
+    bench_coalesce_loop_u32,
+    bench_coalesce_slice_u32,
+    bench_replace_none_loop_f64,
+    bench_replace_none_slice_f64,
+    bench_zip_with_loop_u32,
+    bench_zip_with_slice_u32
 );
 
 benchmark_main!(bench);
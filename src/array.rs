@@ -0,0 +1,256 @@
+//! A sentinel-terminated inline array
+//!
+//! [`OptionedArray<T, N>`](struct.OptionedArray.html) holds up to `N` values
+//! inline, without a separate length field: like a C string, the list ends
+//! at the first None value. This makes it as small as `[T; N]`:
+//!
+//! ```
+//!# use optional::array::OptionedArray;
+//! let mut neighbors = OptionedArray::<u32, 16>::new();
+//! neighbors.push(7);
+//! neighbors.push(3);
+//! assert_eq!(&[7, 3], neighbors.as_slice());
+//! assert_eq!(64, std::mem::size_of::<OptionedArray<u32, 16>>());
+//! ```
+//!
+//! The array keeps all None values after the last element, so the length
+//! is found with a binary search.
+
+use super::{none, wrap, Noned, Optioned};
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::slice::Iter;
+
+/// An inline array of up to `N` values whose end is marked by the first
+/// None value.
+#[derive(Copy, Clone)]
+pub struct OptionedArray<T: Noned + Copy, const N: usize> {
+    // invariant: no None value precedes a value
+    values: [T; N],
+}
+
+impl<T: Noned + Copy, const N: usize> OptionedArray<T, N> {
+    /// Creates an empty array.
+    #[inline]
+    pub fn new() -> Self {
+        OptionedArray {
+            values: [T::get_none(); N],
+        }
+    }
+
+    /// Returns the number of values, which is the index of the first None
+    /// value, or `N` if there is none.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.partition_point(|v| !v.is_none())
+    }
+
+    /// Returns `true` if the array holds no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        N == 0 || self.values[0].is_none()
+    }
+
+    /// Returns `true` if no more values fit in the array.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        N == 0 || !self.values[N - 1].is_none()
+    }
+
+    /// Returns the maximum number of values, `N`.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Appends a value, or returns it as an error if the array is full.
+    ///
+    /// # Panics
+    ///
+    /// if the value is the None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::array::OptionedArray;
+    /// let mut a = OptionedArray::<i8, 1>::new();
+    /// assert_eq!(Ok(()), a.try_push(1));
+    /// assert_eq!(Err(2), a.try_push(2));
+    /// ```
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        assert!(!value.is_none(), "cannot push the None value");
+        let len = self.len();
+        if len == N {
+            return Err(value);
+        }
+        self.values[len] = value;
+        Ok(())
+    }
+
+    /// Appends a value.
+    ///
+    /// # Panics
+    ///
+    /// if the value is the None value or the array is full
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        if self.try_push(value).is_err() {
+            panic!("array is full");
+        }
+    }
+
+    /// Removes and returns the last value, or `None` if the array is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::array::OptionedArray;
+    /// let mut a: OptionedArray<u16, 4> = vec![1, 2].into_iter().collect();
+    /// assert_eq!(some(2), a.pop());
+    /// assert_eq!(some(1), a.pop());
+    /// assert_eq!(none(), a.pop());
+    /// ```
+    pub fn pop(&mut self) -> Optioned<T> {
+        match self.len() {
+            0 => none(),
+            len => wrap(std::mem::replace(&mut self.values[len - 1], T::get_none())),
+        }
+    }
+
+    /// Inserts a value at `index`, shifting all values after it to the
+    /// right.
+    ///
+    /// # Panics
+    ///
+    /// if the value is the None value, `index > len()` or the array is full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::array::OptionedArray;
+    /// let mut a: OptionedArray<char, 4> = "ac".chars().collect();
+    /// a.insert(1, 'b');
+    /// assert_eq!(&['a', 'b', 'c'], a.as_slice());
+    /// ```
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(!value.is_none(), "cannot insert the None value");
+        let len = self.len();
+        assert!(index <= len, "index {} out of bounds", index);
+        assert!(len < N, "array is full");
+        self.values[index..=len].rotate_right(1);
+        self.values[index] = value;
+    }
+
+    /// Removes and returns the value at `index`, shifting all values after
+    /// it to the left.
+    ///
+    /// # Panics
+    ///
+    /// if `index >= len()`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::array::OptionedArray;
+    /// let mut a: OptionedArray<u8, 3> = vec![1, 2, 3].into_iter().collect();
+    /// assert_eq!(1, a.remove(0));
+    /// assert_eq!(&[2, 3], a.as_slice());
+    /// ```
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(index < len, "index {} out of bounds", index);
+        let value = self.values[index];
+        self.values[index..len].rotate_left(1);
+        self.values[len - 1] = T::get_none();
+        value
+    }
+
+    /// Returns the value at `index`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Optioned<T> {
+        self.values.get(index).map_or_else(none, |&v| wrap(v))
+    }
+
+    /// Shortens the array to at most `len` values.
+    pub fn truncate(&mut self, len: usize) {
+        if len < N {
+            for v in &mut self.values[len..] {
+                *v = T::get_none();
+            }
+        }
+    }
+
+    /// Removes all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Returns the values as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.values[..self.len()]
+    }
+
+    /// Returns an iterator over the values.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.as_slice().iter()
+    }
+}
+
+impl<T: Noned + Copy, const N: usize> Default for OptionedArray<T, N> {
+    #[inline]
+    fn default() -> Self {
+        OptionedArray::new()
+    }
+}
+
+impl<T: Noned + Copy + PartialEq, const N: usize> PartialEq for OptionedArray<T, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Noned + Copy + Eq, const N: usize> Eq for OptionedArray<T, N> {}
+
+impl<T: Noned + Copy + Hash, const N: usize> Hash for OptionedArray<T, N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: Noned + Copy + Debug, const N: usize> Debug for OptionedArray<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Collects up to `N` values.
+///
+/// # Panics
+///
+/// if the iterator yields the None value or more than `N` values
+impl<T: Noned + Copy, const N: usize> FromIterator<T> for OptionedArray<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array = OptionedArray::new();
+        for v in iter {
+            array.push(v);
+        }
+        array
+    }
+}
+
+impl<'a, T: Noned + Copy, const N: usize> IntoIterator for &'a OptionedArray<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
@@ -0,0 +1,592 @@
+//! Atomic optional values
+//!
+//! [`AtomicOptioned<T>`](struct.AtomicOptioned.html) is an `Optioned<T>` that
+//! can be shared between threads. It stores the None value in the atomic
+//! integer itself, so it is exactly as large as the atomic type and never
+//! needs a lock:
+//!
+//! ```
+//!# use optional::{some, none};
+//!# use optional::atomic::AtomicOptioned;
+//! use std::sync::atomic::Ordering::SeqCst;
+//!
+//! let current_job = AtomicOptioned::<u64>::default();
+//! assert_eq!(Ok(()), current_job.set_if_none(42, SeqCst));
+//! assert_eq!(Err(42), current_job.set_if_none(7, SeqCst));
+//! assert_eq!(some(42), current_job.take(SeqCst));
+//! assert_eq!(none(), current_job.load(SeqCst));
+//! assert_eq!(8, std::mem::size_of::<AtomicOptioned<u64>>());
+//! ```
+//!
+//! [`AtomicOptionBool`](struct.AtomicOptionBool.html) does the same for
+//! `OptionBool`, using a single `AtomicU8`.
+
+use super::{wrap, Noned, OptionBool, Optioned};
+use std::fmt::{self, Debug};
+use std::sync::atomic::{
+    AtomicI16, AtomicI32, AtomicI8, AtomicIsize, AtomicU16, AtomicU32, AtomicU8, AtomicUsize,
+    Ordering,
+};
+#[cfg(target_has_atomic = "64")]
+use std::sync::atomic::{AtomicI64, AtomicU64};
+
+/// Types that can be stored in one of the atomic integers of
+/// `std::sync::atomic`: the integer types, and `f32` and `f64` as their bit
+/// patterns.
+///
+/// The methods mirror those of the atomic types; they are only needed to
+/// implement `AtomicOptioned`.
+pub trait AtomicNoned: Noned + Copy {
+    /// The atomic type holding a `Self`
+    type Atomic: Send + Sync;
+
+    /// An atomic holding the None value, to initialize constants and
+    /// statics
+    const NONE: Self::Atomic;
+
+    /// Creates an atomic holding `value`.
+    fn new_atomic(value: Self) -> Self::Atomic;
+
+    /// Unwraps the atomic.
+    fn into_inner(atomic: Self::Atomic) -> Self;
+
+    /// Loads the value.
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+
+    /// Stores a value.
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+
+    /// Stores a value, returning the previous one.
+    fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+
+    /// Stores `new` if the current value is `current`.
+    fn compare_exchange(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+
+    /// Stores `new` if the current value is `current`, possibly failing
+    /// spuriously.
+    fn compare_exchange_weak(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+}
+
+macro_rules! atomic_noned {
+    ($($(#[$attr:meta])* $t:ty => $atomic:ty = $none:ident),*) => {
+        $(
+            $(#[$attr])*
+            impl AtomicNoned for $t {
+                type Atomic = $atomic;
+
+                const NONE: $atomic = <$atomic>::new(<$t>::$none);
+
+                #[inline]
+                fn new_atomic(value: $t) -> $atomic {
+                    <$atomic>::new(value)
+                }
+
+                #[inline]
+                fn into_inner(atomic: $atomic) -> $t {
+                    atomic.into_inner()
+                }
+
+                #[inline]
+                fn load(atomic: &$atomic, order: Ordering) -> $t {
+                    atomic.load(order)
+                }
+
+                #[inline]
+                fn store(atomic: &$atomic, value: $t, order: Ordering) {
+                    atomic.store(value, order)
+                }
+
+                #[inline]
+                fn swap(atomic: &$atomic, value: $t, order: Ordering) -> $t {
+                    atomic.swap(value, order)
+                }
+
+                #[inline]
+                fn compare_exchange(
+                    atomic: &$atomic,
+                    current: $t,
+                    new: $t,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<$t, $t> {
+                    atomic.compare_exchange(current, new, success, failure)
+                }
+
+                #[inline]
+                fn compare_exchange_weak(
+                    atomic: &$atomic,
+                    current: $t,
+                    new: $t,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<$t, $t> {
+                    atomic.compare_exchange_weak(current, new, success, failure)
+                }
+            }
+        )*
+    };
+}
+
+atomic_noned!(
+    u8 => AtomicU8 = MAX,
+    u16 => AtomicU16 = MAX,
+    u32 => AtomicU32 = MAX,
+    #[cfg(target_has_atomic = "64")]
+    u64 => AtomicU64 = MAX,
+    usize => AtomicUsize = MAX,
+    i8 => AtomicI8 = MIN,
+    i16 => AtomicI16 = MIN,
+    i32 => AtomicI32 = MIN,
+    #[cfg(target_has_atomic = "64")]
+    i64 => AtomicI64 = MIN,
+    isize => AtomicIsize = MIN
+);
+
+// Floats are stored as bits, so compare-exchange compares bit patterns
+// (`0.0` and `-0.0` differ). Every NaN is stored as the None value, so
+// exchanging `None` works whichever NaN an `Optioned` holds.
+macro_rules! _bits {
+    ($t:ident, $value:expr) => {
+        if $value.is_nan() {
+            $t::get_none().to_bits()
+        } else {
+            $value.to_bits()
+        }
+    };
+}
+
+macro_rules! atomic_noned_float {
+    ($($(#[$attr:meta])* $t:ident => $atomic:ty),*) => {
+        $(
+            $(#[$attr])*
+            impl AtomicNoned for $t {
+                type Atomic = $atomic;
+
+                const NONE: $atomic = <$atomic>::new($t::NAN.to_bits());
+
+                #[inline]
+                fn new_atomic(value: $t) -> $atomic {
+                    <$atomic>::new(_bits!($t, value))
+                }
+
+                #[inline]
+                fn into_inner(atomic: $atomic) -> $t {
+                    $t::from_bits(atomic.into_inner())
+                }
+
+                #[inline]
+                fn load(atomic: &$atomic, order: Ordering) -> $t {
+                    $t::from_bits(atomic.load(order))
+                }
+
+                #[inline]
+                fn store(atomic: &$atomic, value: $t, order: Ordering) {
+                    atomic.store(_bits!($t, value), order)
+                }
+
+                #[inline]
+                fn swap(atomic: &$atomic, value: $t, order: Ordering) -> $t {
+                    $t::from_bits(atomic.swap(_bits!($t, value), order))
+                }
+
+                #[inline]
+                fn compare_exchange(
+                    atomic: &$atomic,
+                    current: $t,
+                    new: $t,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<$t, $t> {
+                    atomic
+                        .compare_exchange(_bits!($t, current), _bits!($t, new), success, failure)
+                        .map($t::from_bits)
+                        .map_err($t::from_bits)
+                }
+
+                #[inline]
+                fn compare_exchange_weak(
+                    atomic: &$atomic,
+                    current: $t,
+                    new: $t,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<$t, $t> {
+                    atomic
+                        .compare_exchange_weak(_bits!($t, current), _bits!($t, new), success, failure)
+                        .map($t::from_bits)
+                        .map_err($t::from_bits)
+                }
+            }
+        )*
+    };
+}
+
+atomic_noned_float!(
+    f32 => AtomicU32,
+    #[cfg(target_has_atomic = "64")]
+    f64 => AtomicU64
+);
+
+// the strongest ordering a failed compare-exchange may use for `order`
+#[inline]
+fn _failure_ordering(order: Ordering) -> Ordering {
+    match order {
+        Ordering::Release => Ordering::Relaxed,
+        Ordering::AcqRel => Ordering::Acquire,
+        order => order,
+    }
+}
+
+/// An `Optioned<T>` that can be safely shared between threads.
+pub struct AtomicOptioned<T: AtomicNoned> {
+    atomic: T::Atomic,
+}
+
+impl<T: AtomicNoned> AtomicOptioned<T> {
+    /// Creates a new `AtomicOptioned`.
+    #[inline]
+    pub fn new(value: Optioned<T>) -> Self {
+        AtomicOptioned {
+            atomic: T::new_atomic(value.value),
+        }
+    }
+
+    /// Creates an `AtomicOptioned` holding `None`. Unlike `default`, this
+    /// can initialize statics.
+    #[inline]
+    pub const fn none() -> Self {
+        AtomicOptioned { atomic: T::NONE }
+    }
+
+    /// Consumes the atomic and returns the contained value.
+    #[inline]
+    pub fn into_inner(self) -> Optioned<T> {
+        wrap(T::into_inner(self.atomic))
+    }
+
+    /// Loads the value.
+    #[inline]
+    pub fn load(&self, order: Ordering) -> Optioned<T> {
+        wrap(T::load(&self.atomic, order))
+    }
+
+    /// Stores a value.
+    #[inline]
+    pub fn store(&self, value: Optioned<T>, order: Ordering) {
+        T::store(&self.atomic, value.value, order)
+    }
+
+    /// Stores a value, returning the previous one.
+    #[inline]
+    pub fn swap(&self, value: Optioned<T>, order: Ordering) -> Optioned<T> {
+        wrap(T::swap(&self.atomic, value.value, order))
+    }
+
+    /// Takes the value out, leaving `None` in its place.
+    #[inline]
+    pub fn take(&self, order: Ordering) -> Optioned<T> {
+        wrap(T::swap(&self.atomic, T::get_none(), order))
+    }
+
+    /// Stores `new` if the current value is `current`. Returns the previous
+    /// value, as `Ok` if it was replaced and as `Err` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::atomic::AtomicOptioned;
+    /// use std::sync::atomic::Ordering::{Acquire, Relaxed};
+    ///
+    /// let a = AtomicOptioned::new(some(5i32));
+    /// assert_eq!(Err(some(5)), a.compare_exchange(none(), some(6), Acquire, Relaxed));
+    /// assert_eq!(Ok(some(5)), a.compare_exchange(some(5), none(), Acquire, Relaxed));
+    /// assert_eq!(none(), a.load(Relaxed));
+    /// ```
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: Optioned<T>,
+        new: Optioned<T>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Optioned<T>, Optioned<T>> {
+        T::compare_exchange(&self.atomic, current.value, new.value, success, failure)
+            .map(wrap)
+            .map_err(wrap)
+    }
+
+    /// Like `compare_exchange`, but may fail spuriously, which allows
+    /// more efficient code on some platforms when called in a loop.
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: Optioned<T>,
+        new: Optioned<T>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Optioned<T>, Optioned<T>> {
+        T::compare_exchange_weak(&self.atomic, current.value, new.value, success, failure)
+            .map(wrap)
+            .map_err(wrap)
+    }
+
+    /// Repeatedly applies `f` to the current value and tries to store the
+    /// result until it succeeds or `f` returns `None`. Returns the previous
+    /// value, as `Ok` if it was replaced and as `Err` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::atomic::AtomicOptioned;
+    /// use std::sync::atomic::Ordering::SeqCst;
+    ///
+    /// let a = AtomicOptioned::new(some(1u16));
+    /// let inc = |x: optional::Optioned<u16>| Some(x.map_or(some(0), |x| some(x + 1)));
+    /// assert_eq!(Ok(some(1)), a.fetch_update(SeqCst, SeqCst, inc));
+    /// assert_eq!(some(2), a.load(SeqCst));
+    /// assert_eq!(Err(some(2)), a.fetch_update(SeqCst, SeqCst, |_| None));
+    /// ```
+    pub fn fetch_update<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<Optioned<T>, Optioned<T>>
+    where
+        F: FnMut(Optioned<T>) -> Option<Optioned<T>>,
+    {
+        let mut prev = self.load(fetch_order);
+        while let Some(next) = f(prev) {
+            match self.compare_exchange_weak(prev, next, set_order, fetch_order) {
+                Ok(prev) => return Ok(prev),
+                Err(actual) => prev = actual,
+            }
+        }
+        Err(prev)
+    }
+
+    /// Stores `value` if the current value is `None`. Returns the current
+    /// value as an error otherwise.
+    ///
+    /// # Panics
+    ///
+    /// if `value` is the None value
+    #[inline]
+    pub fn set_if_none(&self, value: T, order: Ordering) -> Result<(), T> {
+        assert!(!value.is_none(), "cannot set the None value");
+        T::compare_exchange(
+            &self.atomic,
+            T::get_none(),
+            value,
+            order,
+            _failure_ordering(order),
+        )
+        .map(|_| ())
+    }
+}
+
+impl<T: AtomicNoned> Default for AtomicOptioned<T> {
+    /// Creates an `AtomicOptioned` holding `None`.
+    #[inline]
+    fn default() -> Self {
+        AtomicOptioned::none()
+    }
+}
+
+impl<T: AtomicNoned> From<Optioned<T>> for AtomicOptioned<T> {
+    #[inline]
+    fn from(value: Optioned<T>) -> Self {
+        AtomicOptioned::new(value)
+    }
+}
+
+impl<T: AtomicNoned + Debug> Debug for AtomicOptioned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.load(Ordering::Relaxed).fmt(f)
+    }
+}
+
+// False < None < True, so that Kleene `and` is the minimum and `or` the
+// maximum of two values
+const OB_FALSE: u8 = 0;
+const OB_NONE: u8 = 1;
+const OB_TRUE: u8 = 2;
+
+#[inline]
+fn _ob_encode(value: OptionBool) -> u8 {
+    match value {
+        OptionBool::SomeFalse => OB_FALSE,
+        OptionBool::None => OB_NONE,
+        OptionBool::SomeTrue => OB_TRUE,
+    }
+}
+
+#[inline]
+fn _ob_decode(value: u8) -> OptionBool {
+    match value {
+        OB_FALSE => OptionBool::SomeFalse,
+        OB_TRUE => OptionBool::SomeTrue,
+        _ => OptionBool::None,
+    }
+}
+
+/// An `OptionBool` that can be safely shared between threads, e.g. a
+/// tri-state flag that is unknown until first set.
+///
+/// # Examples
+///
+/// ```
+///# use optional::OptionBool;
+///# use optional::atomic::AtomicOptionBool;
+/// use std::sync::atomic::Ordering::SeqCst;
+///
+/// let healthy = AtomicOptionBool::default();
+/// assert_eq!(OptionBool::None, healthy.load(SeqCst));
+/// assert_eq!(Ok(()), healthy.set_if_unknown(true, SeqCst));
+/// assert_eq!(Err(true), healthy.set_if_unknown(false, SeqCst));
+/// ```
+pub struct AtomicOptionBool {
+    atomic: AtomicU8,
+}
+
+impl AtomicOptionBool {
+    /// Creates a new `AtomicOptionBool`.
+    #[inline]
+    pub fn new(value: OptionBool) -> Self {
+        AtomicOptionBool {
+            atomic: AtomicU8::new(_ob_encode(value)),
+        }
+    }
+
+    /// Consumes the atomic and returns the contained value.
+    #[inline]
+    pub fn into_inner(self) -> OptionBool {
+        _ob_decode(self.atomic.into_inner())
+    }
+
+    /// Loads the value.
+    #[inline]
+    pub fn load(&self, order: Ordering) -> OptionBool {
+        _ob_decode(self.atomic.load(order))
+    }
+
+    /// Stores a value.
+    #[inline]
+    pub fn store(&self, value: OptionBool, order: Ordering) {
+        self.atomic.store(_ob_encode(value), order)
+    }
+
+    /// Stores a value, returning the previous one.
+    #[inline]
+    pub fn swap(&self, value: OptionBool, order: Ordering) -> OptionBool {
+        _ob_decode(self.atomic.swap(_ob_encode(value), order))
+    }
+
+    /// Stores `new` if the current value is `current`. Returns the previous
+    /// value, as `Ok` if it was replaced and as `Err` otherwise.
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: OptionBool,
+        new: OptionBool,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<OptionBool, OptionBool> {
+        self.atomic
+            .compare_exchange(_ob_encode(current), _ob_encode(new), success, failure)
+            .map(_ob_decode)
+            .map_err(_ob_decode)
+    }
+
+    /// Stores `value` if the current value is `None`. Returns the current
+    /// value as an error otherwise.
+    #[inline]
+    pub fn set_if_unknown(&self, value: bool, order: Ordering) -> Result<(), bool> {
+        match self.compare_exchange(
+            OptionBool::None,
+            OptionBool::from(Some(value)),
+            order,
+            _failure_ordering(order),
+        ) {
+            Ok(_) => Ok(()),
+            Err(current) => Err(current.unwrap()),
+        }
+    }
+
+    /// Stores the three-valued (Kleene) conjunction of the current value
+    /// and `value`, returning the previous value. The result is `false` if
+    /// either is `false`, else `None` if either is `None`, else `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool::*;
+    ///# use optional::atomic::AtomicOptionBool;
+    /// use std::sync::atomic::Ordering::SeqCst;
+    ///
+    /// let all_healthy = AtomicOptionBool::new(SomeTrue);
+    /// assert_eq!(SomeTrue, all_healthy.fetch_and(None, SeqCst));
+    /// assert_eq!(None, all_healthy.fetch_and(SomeFalse, SeqCst));
+    /// assert_eq!(SomeFalse, all_healthy.load(SeqCst));
+    /// ```
+    #[inline]
+    pub fn fetch_and(&self, value: OptionBool, order: Ordering) -> OptionBool {
+        _ob_decode(self.atomic.fetch_min(_ob_encode(value), order))
+    }
+
+    /// Stores the three-valued (Kleene) disjunction of the current value
+    /// and `value`, returning the previous value. The result is `true` if
+    /// either is `true`, else `None` if either is `None`, else `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool::*;
+    ///# use optional::atomic::AtomicOptionBool;
+    /// use std::sync::atomic::Ordering::SeqCst;
+    ///
+    /// let any_failed = AtomicOptionBool::new(SomeFalse);
+    /// assert_eq!(SomeFalse, any_failed.fetch_or(None, SeqCst));
+    /// assert_eq!(None, any_failed.fetch_or(SomeTrue, SeqCst));
+    /// assert_eq!(SomeTrue, any_failed.load(SeqCst));
+    /// ```
+    #[inline]
+    pub fn fetch_or(&self, value: OptionBool, order: Ordering) -> OptionBool {
+        _ob_decode(self.atomic.fetch_max(_ob_encode(value), order))
+    }
+}
+
+impl Default for AtomicOptionBool {
+    /// Creates an `AtomicOptionBool` holding `None`.
+    #[inline]
+    fn default() -> Self {
+        AtomicOptionBool::new(OptionBool::None)
+    }
+}
+
+impl From<OptionBool> for AtomicOptionBool {
+    #[inline]
+    fn from(value: OptionBool) -> Self {
+        AtomicOptionBool::new(value)
+    }
+}
+
+impl Debug for AtomicOptionBool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.load(Ordering::Relaxed).fmt(f)
+    }
+}
@@ -0,0 +1,170 @@
+//! A mutable memory location holding an `Optioned`
+//!
+//! [`OptionedCell<T>`](struct.OptionedCell.html) is a `Cell<Optioned<T>>`
+//! with methods that work on the optional value, for `Optioned` fields that
+//! are shared, e.g. through `Rc`. Like `Optioned<T>`, it is no larger than
+//! `T`:
+//!
+//! ```
+//!# use optional::{some, none};
+//!# use optional::cell::OptionedCell;
+//! use std::rc::Rc;
+//!
+//! let parent = Rc::new(OptionedCell::<u32>::default());
+//! let child = parent.clone();
+//! child.set(some(3));
+//! assert_eq!(some(3), parent.take());
+//! assert_eq!(none(), child.get());
+//! assert_eq!(4, std::mem::size_of::<OptionedCell<u32>>());
+//! ```
+
+use super::{none, some, Noned, OptEq, Optioned};
+use std::cell::Cell;
+use std::fmt::{self, Debug};
+
+/// A `Cell` holding an `Optioned<T>`.
+pub struct OptionedCell<T: Noned + Copy> {
+    cell: Cell<Optioned<T>>,
+}
+
+impl<T: Noned + Copy> OptionedCell<T> {
+    /// Creates a cell holding `value`.
+    #[inline]
+    pub fn new(value: Optioned<T>) -> Self {
+        OptionedCell {
+            cell: Cell::new(value),
+        }
+    }
+
+    /// Returns the value.
+    #[inline]
+    pub fn get(&self) -> Optioned<T> {
+        self.cell.get()
+    }
+
+    /// Sets the value.
+    #[inline]
+    pub fn set(&self, value: Optioned<T>) {
+        self.cell.set(value)
+    }
+
+    /// Returns `true` if the cell holds a value.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.get().is_some()
+    }
+
+    /// Returns `true` if the cell holds `None`.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.get().is_none()
+    }
+
+    /// Takes the value out, leaving `None` in its place.
+    #[inline]
+    pub fn take(&self) -> Optioned<T> {
+        self.cell.replace(none())
+    }
+
+    /// Sets the value, returning the previous one.
+    #[inline]
+    pub fn replace(&self, value: Optioned<T>) -> Optioned<T> {
+        self.cell.replace(value)
+    }
+
+    /// Returns the value, first setting it to `f()` if the cell holds
+    /// `None`.
+    ///
+    /// # Panics
+    ///
+    /// if `f` returns the None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::some;
+    ///# use optional::cell::OptionedCell;
+    /// let depth = OptionedCell::default();
+    /// assert_eq!(2, depth.get_or_insert_with(|| 2i8));
+    /// assert_eq!(2, depth.get_or_insert_with(|| 5));
+    /// assert_eq!(some(2), depth.get());
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce() -> T>(&self, f: F) -> T {
+        let value = self.get();
+        if value.is_some() {
+            return value.unpack();
+        }
+        let value = f();
+        self.set(some(value));
+        value
+    }
+
+    /// Sets the value to `f` of the current value and returns the new
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::cell::OptionedCell;
+    /// let visits = OptionedCell::new(some(1u16));
+    /// assert_eq!(some(2), visits.update(|v| v.map_t(|n| n + 1)));
+    /// assert_eq!(none(), visits.update(|v| v.filter(|n| n > 2)));
+    /// ```
+    #[inline]
+    pub fn update<F: FnOnce(Optioned<T>) -> Optioned<T>>(&self, f: F) -> Optioned<T> {
+        let value = f(self.get());
+        self.set(value);
+        value
+    }
+
+    /// Returns a mutable reference to the value. This is checked at compile
+    /// time, since it needs a mutable reference to the cell.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Optioned<T> {
+        self.cell.get_mut()
+    }
+
+    /// Consumes the cell and returns the value.
+    #[inline]
+    pub fn into_inner(self) -> Optioned<T> {
+        self.cell.into_inner()
+    }
+}
+
+impl<T: Noned + Copy> Clone for OptionedCell<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        OptionedCell::new(self.get())
+    }
+}
+
+impl<T: Noned + Copy> Default for OptionedCell<T> {
+    /// Creates a cell holding `None`.
+    #[inline]
+    fn default() -> Self {
+        OptionedCell::new(none())
+    }
+}
+
+impl<T: Noned + Copy> From<Optioned<T>> for OptionedCell<T> {
+    #[inline]
+    fn from(value: Optioned<T>) -> Self {
+        OptionedCell::new(value)
+    }
+}
+
+impl<T: Noned + Copy + OptEq> PartialEq for OptionedCell<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<T: Noned + Copy + OptEq> Eq for OptionedCell<T> {}
+
+impl<T: Noned + Copy + Debug> Debug for OptionedCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
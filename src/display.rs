@@ -0,0 +1,160 @@
+//! Printing and parsing `Optioned` values
+//!
+//! `Optioned<T>` implements `Display` and `FromStr` whenever `T` does. The
+//! value is formatted with all flags (width, precision, etc.) passed on to
+//! `T`, while `None` is printed as an empty string, padded to the width:
+//!
+//! ```
+//!# use optional::{Optioned, some, none};
+//! assert_eq!(" 1.50|    ", format!("{:5.2}|{:4}", some(1.5f32), none::<f32>()));
+//! assert_eq!(Ok(some(-4i16)), "-4".parse());
+//! assert_eq!(Ok(none()), "".parse::<Optioned<char>>());
+//! ```
+//!
+//! To use another marker for `None`, e.g. `"NULL"`, `"NA"` or `"-"`, print
+//! via [`Optioned::display_none_as`](../struct.Optioned.html#method.display_none_as)
+//! and parse via [`Optioned::parse_none_as`](../struct.Optioned.html#method.parse_none_as).
+//!
+//! Parsing never maps a value to `None` behind the caller's back: text that
+//! parses to the None value itself, e.g. `"255"` for `u8` or `"NaN"` for
+//! floats, is an error.
+
+use super::{none, Noned, Optioned, SentinelError};
+use std::error;
+use std::fmt::{self, Debug, Display, Write};
+use std::str::FromStr;
+
+/// The error returned when parsing an `Optioned<T>` fails.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseOptionedError<T, E> {
+    /// the text is neither the None marker nor a valid `T`
+    Invalid(E),
+    /// the text is the None value of `T`, which cannot be a value
+    Sentinel(SentinelError<T>),
+}
+
+impl<T, E: Display> Display for ParseOptionedError<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseOptionedError::Invalid(ref e) => e.fmt(f),
+            ParseOptionedError::Sentinel(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl<T: Debug, E: error::Error + 'static> error::Error for ParseOptionedError<T, E> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ParseOptionedError::Invalid(ref e) => Some(e),
+            ParseOptionedError::Sentinel(_) => None,
+        }
+    }
+}
+
+/// Formats an `Optioned<T>`, printing a chosen marker for `None`. Created
+/// by [`Optioned::display_none_as`](../struct.Optioned.html#method.display_none_as).
+#[derive(Copy, Clone, Debug)]
+pub struct OptionedDisplay<'a, T: Noned + Copy> {
+    value: Optioned<T>,
+    none: &'a str,
+}
+
+impl<'a, T: Noned + Copy + Display> Display for OptionedDisplay<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.value.is_none() {
+            pad(f, self.none)
+        } else {
+            self.value.value.fmt(f)
+        }
+    }
+}
+
+// like `Formatter::pad`, but without truncating to the precision, which is
+// meant for the value
+fn pad(f: &mut fmt::Formatter, marker: &str) -> fmt::Result {
+    let len = marker.chars().count();
+    let padding = f.width().map_or(0, |width| width.saturating_sub(len));
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(marker)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+impl<T: Noned + Copy + Display> Display for Optioned<T> {
+    /// Formats the value, or an empty string for `None`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.display_none_as("").fmt(f)
+    }
+}
+
+impl<T: Noned + Copy + FromStr> FromStr for Optioned<T> {
+    type Err = ParseOptionedError<T, T::Err>;
+
+    /// Parses the empty string as `None`, and anything else as `T`. Text
+    /// that parses to the None value (e.g. `"NaN"`) is an error.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Optioned::parse_none_as(s, "")
+    }
+}
+
+impl<T: Noned + Copy> Optioned<T> {
+    /// Returns an adapter that formats the value like `Display` does, but
+    /// prints `marker` for `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// let row = [some(3u32), none(), some(12)];
+    /// let cells: Vec<_> = row.iter().map(|c| format!("{:>4}", c.display_none_as("NA"))).collect();
+    /// assert_eq!("   3,  NA,  12", cells.join(","));
+    /// ```
+    #[inline]
+    pub fn display_none_as(self, marker: &str) -> OptionedDisplay<'_, T> {
+        OptionedDisplay {
+            value: self,
+            none: marker,
+        }
+    }
+
+    /// Parses `marker` as `None`, and anything else as `T`, like `FromStr`
+    /// does for the empty string.
+    ///
+    /// # Errors
+    ///
+    /// if the text is not a valid `T`, or parses to the None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{Optioned, some, none};
+    ///# use optional::display::ParseOptionedError;
+    /// assert_eq!(Ok(none()), Optioned::<f64>::parse_none_as("NULL", "NULL"));
+    /// assert_eq!(Ok(some(0.25)), Optioned::<f64>::parse_none_as("0.25", "NULL"));
+    /// assert!(Optioned::<u8>::parse_none_as("", "-").is_err());
+    /// match Optioned::<u8>::parse_none_as("255", "-") {
+    ///     Err(ParseOptionedError::Sentinel(e)) => assert_eq!(255, e.value),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn parse_none_as(s: &str, marker: &str) -> Result<Self, ParseOptionedError<T, T::Err>>
+    where
+        T: FromStr,
+    {
+        if s == marker {
+            return Ok(none());
+        }
+        let value = s.parse::<T>().map_err(ParseOptionedError::Invalid)?;
+        Optioned::try_some(value).map_err(ParseOptionedError::Sentinel)
+    }
+}
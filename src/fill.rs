@@ -0,0 +1,551 @@
+//! Filling gaps in sequences of `Optioned` values
+//!
+//! A *gap* is a run of consecutive `None`s. The functions in this module
+//! replace the `None`s within a gap by the last value before it (`ffill`),
+//! the first value after it (`bfill`), a constant (`fill_with`) or a linear
+//! interpolation between the values on both sides (`interpolate_linear`).
+//!
+//! Every function takes a `limit`: with `Some(n)`, at most `n` `None`s of
+//! each gap are filled (counted from the side the fill value comes from),
+//! the rest stay `None`. With `None`, whole gaps are filled.
+//!
+//! The in-place functions work on `&mut [Optioned<T>]`, the `_iter`
+//! variants wrap any iterator of `Optioned<T>` and need only constant
+//! memory regardless of the length of the gaps.
+//!
+//! # Examples
+//!
+//! ```
+//!# use optional::{some, none};
+//!# use optional::fill::ffill;
+//! let mut v = [some(1u32), none(), none(), some(4), none()];
+//! ffill(&mut v, Some(1));
+//! assert_eq!([some(1), some(1), none(), some(4), some(4)], v);
+//! ```
+
+use super::{none, wrap, Noned, Optioned};
+use std::iter::Fuse;
+
+/// Types that allow linear interpolation between two values.
+///
+/// This is implemented for all the primitive integer and float types that
+/// implement `Noned`. Integer interpolation rounds toward `a`.
+pub trait Interpolate: Noned + Copy {
+    /// Returns the value `step / steps` of the way from `a` to `b`.
+    fn interpolate(a: Self, b: Self, step: usize, steps: usize) -> Self;
+}
+
+impl Interpolate for u8 {
+    #[inline]
+    fn interpolate(a: u8, b: u8, step: usize, steps: usize) -> u8 {
+        _interpolate_int(a as i128, b as i128, step, steps) as u8
+    }
+}
+
+impl Interpolate for u16 {
+    #[inline]
+    fn interpolate(a: u16, b: u16, step: usize, steps: usize) -> u16 {
+        _interpolate_int(a as i128, b as i128, step, steps) as u16
+    }
+}
+
+impl Interpolate for u32 {
+    #[inline]
+    fn interpolate(a: u32, b: u32, step: usize, steps: usize) -> u32 {
+        _interpolate_int(a as i128, b as i128, step, steps) as u32
+    }
+}
+
+impl Interpolate for u64 {
+    #[inline]
+    fn interpolate(a: u64, b: u64, step: usize, steps: usize) -> u64 {
+        _interpolate_int(a as i128, b as i128, step, steps) as u64
+    }
+}
+
+impl Interpolate for usize {
+    #[inline]
+    fn interpolate(a: usize, b: usize, step: usize, steps: usize) -> usize {
+        _interpolate_int(a as i128, b as i128, step, steps) as usize
+    }
+}
+
+impl Interpolate for i8 {
+    #[inline]
+    fn interpolate(a: i8, b: i8, step: usize, steps: usize) -> i8 {
+        _interpolate_int(a as i128, b as i128, step, steps) as i8
+    }
+}
+
+impl Interpolate for i16 {
+    #[inline]
+    fn interpolate(a: i16, b: i16, step: usize, steps: usize) -> i16 {
+        _interpolate_int(a as i128, b as i128, step, steps) as i16
+    }
+}
+
+impl Interpolate for i32 {
+    #[inline]
+    fn interpolate(a: i32, b: i32, step: usize, steps: usize) -> i32 {
+        _interpolate_int(a as i128, b as i128, step, steps) as i32
+    }
+}
+
+impl Interpolate for i64 {
+    #[inline]
+    fn interpolate(a: i64, b: i64, step: usize, steps: usize) -> i64 {
+        _interpolate_int(a as i128, b as i128, step, steps) as i64
+    }
+}
+
+impl Interpolate for isize {
+    #[inline]
+    fn interpolate(a: isize, b: isize, step: usize, steps: usize) -> isize {
+        _interpolate_int(a as i128, b as i128, step, steps) as isize
+    }
+}
+
+impl Interpolate for f32 {
+    #[inline]
+    fn interpolate(a: f32, b: f32, step: usize, steps: usize) -> f32 {
+        _interpolate_float(a as f64, b as f64, step, steps) as f32
+    }
+}
+
+impl Interpolate for f64 {
+    #[inline]
+    fn interpolate(a: f64, b: f64, step: usize, steps: usize) -> f64 {
+        _interpolate_float(a, b, step, steps)
+    }
+}
+
+// the result lies between a and b, so it always fits the original type
+#[inline]
+fn _interpolate_int(a: i128, b: i128, step: usize, steps: usize) -> i128 {
+    a + (b - a) * step as i128 / steps as i128
+}
+
+#[inline]
+fn _interpolate_float(a: f64, b: f64, step: usize, steps: usize) -> f64 {
+    a + (b - a) * (step as f64 / steps as f64)
+}
+
+#[inline]
+fn _within(count: usize, limit: Option<usize>) -> bool {
+    match limit {
+        Some(l) => count <= l,
+        None => true,
+    }
+}
+
+// The fill value for the `index`th `None` of a gap of length `len` between
+// `prev` and `next` (either of which is `none()` at the ends).
+#[inline]
+fn _ffill<T: Noned + Copy>(prev: Optioned<T>, index: usize, limit: Option<usize>) -> Optioned<T> {
+    if _within(index + 1, limit) {
+        prev
+    } else {
+        none()
+    }
+}
+
+#[inline]
+fn _bfill<T: Noned + Copy>(
+    next: Optioned<T>,
+    index: usize,
+    len: usize,
+    limit: Option<usize>,
+) -> Optioned<T> {
+    if _within(len - index, limit) {
+        next
+    } else {
+        none()
+    }
+}
+
+#[inline]
+fn _interpolate<T: Interpolate>(
+    prev: Optioned<T>,
+    next: Optioned<T>,
+    index: usize,
+    len: usize,
+    limit: Option<usize>,
+) -> Optioned<T> {
+    if prev.is_some() && next.is_some() && _within(index + 1, limit) {
+        wrap(T::interpolate(prev.value, next.value, index + 1, len + 1))
+    } else {
+        none()
+    }
+}
+
+// Calls `f(prev, next, index, len)` for every `None` in `values` and
+// stores the result.
+fn _fill_gaps<T, F>(values: &mut [Optioned<T>], mut f: F)
+where
+    T: Noned + Copy,
+    F: FnMut(Optioned<T>, Optioned<T>, usize, usize) -> Optioned<T>,
+{
+    let mut start = 0;
+    while start < values.len() {
+        if values[start].is_some() {
+            start += 1;
+            continue;
+        }
+        let end = values[start..]
+            .iter()
+            .position(Optioned::is_some)
+            .map_or(values.len(), |p| start + p);
+        let prev = if start > 0 { values[start - 1] } else { none() };
+        let next = if end < values.len() {
+            values[end]
+        } else {
+            none()
+        };
+        let len = end - start;
+        for (index, v) in values[start..end].iter_mut().enumerate() {
+            *v = f(prev, next, index, len);
+        }
+        start = end;
+    }
+}
+
+/// Replaces each `None` with the last value before it, if any
+/// (last-observation-carried-forward).
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::fill::ffill;
+/// let mut v = [none(), some(1i8), none(), none(), none()];
+/// ffill(&mut v, None);
+/// assert_eq!([none(), some(1), some(1), some(1), some(1)], v);
+///
+/// let mut v = [none(), some(1i8), none(), none(), none()];
+/// ffill(&mut v, Some(2));
+/// assert_eq!([none(), some(1), some(1), some(1), none()], v);
+/// ```
+pub fn ffill<T: Noned + Copy>(values: &mut [Optioned<T>], limit: Option<usize>) {
+    _fill_gaps(values, |prev, _, index, _| _ffill(prev, index, limit))
+}
+
+/// Replaces each `None` with the first value after it, if any
+/// (next-observation-carried-backward).
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::fill::bfill;
+/// let mut v = [none(), none(), none(), some(1.5f32), none()];
+/// bfill(&mut v, None);
+/// assert_eq!([some(1.5), some(1.5), some(1.5), some(1.5), none()], v);
+///
+/// let mut v = [none(), none(), none(), some(1.5f32), none()];
+/// bfill(&mut v, Some(2));
+/// assert_eq!([none(), some(1.5), some(1.5), some(1.5), none()], v);
+/// ```
+pub fn bfill<T: Noned + Copy>(values: &mut [Optioned<T>], limit: Option<usize>) {
+    _fill_gaps(values, |_, next, index, len| {
+        _bfill(next, index, len, limit)
+    })
+}
+
+/// Replaces each `None` with the given value.
+///
+/// # Panics
+///
+/// if `value` is the None value
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::fill::fill_with;
+/// let mut v = [some(3u16), none(), none(), some(4), none()];
+/// fill_with(&mut v, 0, Some(1));
+/// assert_eq!([some(3), some(0), none(), some(4), some(0)], v);
+/// ```
+pub fn fill_with<T: Noned + Copy>(values: &mut [Optioned<T>], value: T, limit: Option<usize>) {
+    let value = Optioned::some(value);
+    _fill_gaps(values, |_, _, index, _| _ffill(value, index, limit))
+}
+
+/// Replaces each `None` between two values by linear interpolation. `None`s
+/// at the start or end of the slice are left alone.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::fill::interpolate_linear;
+/// let mut v = [none(), some(1.0f64), none(), none(), some(4.0), none()];
+/// interpolate_linear(&mut v, None);
+/// assert_eq!([none(), some(1.0), some(2.0), some(3.0), some(4.0), none()], v);
+///
+/// let mut v = [some(10u32), none(), none(), none(), some(20)];
+/// interpolate_linear(&mut v, Some(2));
+/// assert_eq!([some(10), some(12), some(15), none(), some(20)], v);
+/// ```
+pub fn interpolate_linear<T: Interpolate>(values: &mut [Optioned<T>], limit: Option<usize>) {
+    _fill_gaps(values, |prev, next, index, len| {
+        _interpolate(prev, next, index, len, limit)
+    })
+}
+
+// A slot produced by `Gaps`: either a value or the `index`th `None` of a
+// gap of length `len` between `prev` and `next`.
+enum Slot<T: Noned + Copy> {
+    Value(Optioned<T>),
+    Gap {
+        prev: Optioned<T>,
+        next: Optioned<T>,
+        index: usize,
+        len: usize,
+    },
+}
+
+// Walks an iterator, measuring each gap before it is emitted. Only the
+// length of the current gap and the values on both sides are stored.
+struct Gaps<I: Iterator<Item = Optioned<T>>, T: Noned + Copy> {
+    iter: Fuse<I>,
+    prev: Optioned<T>,
+    next: Optioned<T>,
+    index: usize,
+    len: usize,
+}
+
+impl<I: Iterator<Item = Optioned<T>>, T: Noned + Copy> Gaps<I, T> {
+    fn new(iter: I) -> Self {
+        Gaps {
+            iter: iter.fuse(),
+            prev: none(),
+            next: none(),
+            index: 0,
+            len: 0,
+        }
+    }
+
+    fn next_slot(&mut self) -> Option<Slot<T>> {
+        if self.index < self.len {
+            self.index += 1;
+            return Some(self._gap(self.index - 1));
+        }
+        if self.len > 0 {
+            self.index = 0;
+            self.len = 0;
+            if self.next.is_none() {
+                return Option::None;
+            }
+            self.prev = self.next;
+            return Some(Slot::Value(self.next));
+        }
+        let v = self.iter.next()?;
+        if v.is_some() {
+            self.prev = v;
+            return Some(Slot::Value(v));
+        }
+        self.len = 1;
+        self.next = none();
+        for w in &mut self.iter {
+            if w.is_some() {
+                self.next = w;
+                break;
+            }
+            self.len += 1;
+        }
+        self.index = 1;
+        Some(self._gap(0))
+    }
+
+    #[inline]
+    fn _gap(&self, index: usize) -> Slot<T> {
+        Slot::Gap {
+            prev: self.prev,
+            next: self.next,
+            index,
+            len: self.len,
+        }
+    }
+}
+
+/// Iterator returned by [`ffill_iter`](fn.ffill_iter.html)
+pub struct Ffill<I: Iterator<Item = Optioned<T>>, T: Noned + Copy> {
+    gaps: Gaps<I, T>,
+    limit: Option<usize>,
+}
+
+impl<I: Iterator<Item = Optioned<T>>, T: Noned + Copy> Iterator for Ffill<I, T> {
+    type Item = Optioned<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Optioned<T>> {
+        self.gaps.next_slot().map(|slot| match slot {
+            Slot::Value(v) => v,
+            Slot::Gap { prev, index, .. } => _ffill(prev, index, self.limit),
+        })
+    }
+}
+
+/// Iterator returned by [`bfill_iter`](fn.bfill_iter.html)
+pub struct Bfill<I: Iterator<Item = Optioned<T>>, T: Noned + Copy> {
+    gaps: Gaps<I, T>,
+    limit: Option<usize>,
+}
+
+impl<I: Iterator<Item = Optioned<T>>, T: Noned + Copy> Iterator for Bfill<I, T> {
+    type Item = Optioned<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Optioned<T>> {
+        self.gaps.next_slot().map(|slot| match slot {
+            Slot::Value(v) => v,
+            Slot::Gap {
+                next, index, len, ..
+            } => _bfill(next, index, len, self.limit),
+        })
+    }
+}
+
+/// Iterator returned by [`fill_with_iter`](fn.fill_with_iter.html)
+pub struct FillWith<I: Iterator<Item = Optioned<T>>, T: Noned + Copy> {
+    gaps: Gaps<I, T>,
+    value: Optioned<T>,
+    limit: Option<usize>,
+}
+
+impl<I: Iterator<Item = Optioned<T>>, T: Noned + Copy> Iterator for FillWith<I, T> {
+    type Item = Optioned<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Optioned<T>> {
+        self.gaps.next_slot().map(|slot| match slot {
+            Slot::Value(v) => v,
+            Slot::Gap { index, .. } => _ffill(self.value, index, self.limit),
+        })
+    }
+}
+
+/// Iterator returned by
+/// [`interpolate_linear_iter`](fn.interpolate_linear_iter.html)
+pub struct InterpolateLinear<I: Iterator<Item = Optioned<T>>, T: Interpolate> {
+    gaps: Gaps<I, T>,
+    limit: Option<usize>,
+}
+
+impl<I: Iterator<Item = Optioned<T>>, T: Interpolate> Iterator for InterpolateLinear<I, T> {
+    type Item = Optioned<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Optioned<T>> {
+        self.gaps.next_slot().map(|slot| match slot {
+            Slot::Value(v) => v,
+            Slot::Gap {
+                prev,
+                next,
+                index,
+                len,
+            } => _interpolate(prev, next, index, len, self.limit),
+        })
+    }
+}
+
+/// Like [`ffill`](fn.ffill.html), but lazily fills the values of an
+/// iterator.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none, Optioned};
+///# use optional::fill::ffill_iter;
+/// let v = vec![some('a'), none(), none(), some('b')];
+/// let filled: Vec<Optioned<char>> = ffill_iter(v, Some(1)).collect();
+/// assert_eq!(vec![some('a'), some('a'), none(), some('b')], filled);
+/// ```
+pub fn ffill_iter<I, T>(values: I, limit: Option<usize>) -> Ffill<I::IntoIter, T>
+where
+    I: IntoIterator<Item = Optioned<T>>,
+    T: Noned + Copy,
+{
+    Ffill {
+        gaps: Gaps::new(values.into_iter()),
+        limit,
+    }
+}
+
+/// Like [`bfill`](fn.bfill.html), but lazily fills the values of an
+/// iterator.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none, Optioned};
+///# use optional::fill::bfill_iter;
+/// let v = vec![some('a'), none(), none(), some('b')];
+/// let filled: Vec<Optioned<char>> = bfill_iter(v, Some(1)).collect();
+/// assert_eq!(vec![some('a'), none(), some('b'), some('b')], filled);
+/// ```
+pub fn bfill_iter<I, T>(values: I, limit: Option<usize>) -> Bfill<I::IntoIter, T>
+where
+    I: IntoIterator<Item = Optioned<T>>,
+    T: Noned + Copy,
+{
+    Bfill {
+        gaps: Gaps::new(values.into_iter()),
+        limit,
+    }
+}
+
+/// Like [`fill_with`](fn.fill_with.html), but lazily fills the values of an
+/// iterator.
+///
+/// # Panics
+///
+/// if `value` is the None value
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none, Optioned};
+///# use optional::fill::fill_with_iter;
+/// let v = vec![none(), some(2i64), none()];
+/// let filled: Vec<Optioned<i64>> = fill_with_iter(v, -1, None).collect();
+/// assert_eq!(vec![some(-1), some(2), some(-1)], filled);
+/// ```
+pub fn fill_with_iter<I, T>(values: I, value: T, limit: Option<usize>) -> FillWith<I::IntoIter, T>
+where
+    I: IntoIterator<Item = Optioned<T>>,
+    T: Noned + Copy,
+{
+    FillWith {
+        gaps: Gaps::new(values.into_iter()),
+        value: Optioned::some(value),
+        limit,
+    }
+}
+
+/// Like [`interpolate_linear`](fn.interpolate_linear.html), but lazily fills
+/// the values of an iterator.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none, Optioned};
+///# use optional::fill::interpolate_linear_iter;
+/// let v = vec![some(0i32), none(), none(), some(-6)];
+/// let filled: Vec<Optioned<i32>> = interpolate_linear_iter(v, None).collect();
+/// assert_eq!(vec![some(0), some(-2), some(-4), some(-6)], filled);
+/// ```
+pub fn interpolate_linear_iter<I, T>(
+    values: I,
+    limit: Option<usize>,
+) -> InterpolateLinear<I::IntoIter, T>
+where
+    I: IntoIterator<Item = Optioned<T>>,
+    T: Interpolate,
+{
+    InterpolateLinear {
+        gaps: Gaps::new(values.into_iter()),
+        limit,
+    }
+}
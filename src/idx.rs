@@ -0,0 +1,432 @@
+//! Typed indices
+//!
+//! Arena and graph code often stores links as plain integer indices, and
+//! nothing stops a node index from being used to look up an edge. An
+//! [`Idx<Tag>`](struct.Idx.html) is an index that is tagged with the kind of
+//! thing it points to. It is `#[repr(transparent)]` over its integer
+//! representation and implements `Noned`, so `Optioned<Idx<Node>>` is an
+//! optional link that takes no more space than a `u32`:
+//!
+//! ```
+//!# use optional::{Optioned, some, none};
+//!# use optional::idx::{Idx, IdxVec};
+//! struct Node {
+//!     value: i32,
+//!     next: Optioned<Idx<Node>>,
+//! }
+//!
+//! let mut nodes = IdxVec::new();
+//! let last = nodes.push(Node { value: 2, next: none() });
+//! let first = nodes.push(Node { value: 1, next: some(last) });
+//! assert_eq!(some(last), nodes[first].next);
+//! assert_eq!(2, nodes[last].value);
+//! assert_eq!(4, std::mem::size_of::<Optioned<Idx<Node>>>());
+//! ```
+
+use super::Noned;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use std::slice::Iter;
+
+/// Unsigned integer types that can represent an `Idx`. The None value (the
+/// maximum) is not a valid index.
+pub trait IdxRepr: Noned + Copy + Ord + Hash + Debug {
+    /// Converts an index to this type, or returns `None` if it doesn't fit
+    /// or is the None value.
+    fn from_index(index: usize) -> Option<Self>;
+
+    /// Converts this value to a `usize` index.
+    fn index(self) -> usize;
+}
+
+macro_rules! idx_repr {
+    ($($t:ty),*) => {
+        $(
+            impl IdxRepr for $t {
+                #[inline]
+                fn from_index(index: usize) -> Option<$t> {
+                    if index < <$t>::get_none() as usize {
+                        Some(index as $t)
+                    } else {
+                        None
+                    }
+                }
+
+                #[inline]
+                fn index(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+idx_repr!(u8, u16, u32, usize);
+
+// on 32-bit targets u64::MAX as usize would truncate
+impl IdxRepr for u64 {
+    #[inline]
+    fn from_index(index: usize) -> Option<u64> {
+        let index = index as u64;
+        if index.is_none() {
+            None
+        } else {
+            Some(index)
+        }
+    }
+
+    #[inline]
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// An index into a collection of `Tag`s, represented as a `Repr`.
+///
+/// `Tag` is only a marker; an `Idx<Tag>` is `Copy`, `Eq`, `Ord`, `Hash`,
+/// `Send` and `Sync` whatever `Tag` is.
+#[repr(transparent)]
+pub struct Idx<Tag, Repr = u32> {
+    repr: Repr,
+    tag: PhantomData<fn() -> Tag>,
+}
+
+impl<Tag, Repr: IdxRepr> Idx<Tag, Repr> {
+    /// Creates an index.
+    ///
+    /// # Panics
+    ///
+    /// if the index cannot be represented as a `Repr`, or is its None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::idx::Idx;
+    /// struct Edge;
+    /// let i: Idx<Edge> = Idx::new(3);
+    /// assert_eq!(3, i.index());
+    /// ```
+    #[inline]
+    pub fn new(index: usize) -> Self {
+        match Repr::from_index(index) {
+            Some(repr) => Idx::from_repr(repr),
+            None => panic!("index {} out of range", index),
+        }
+    }
+
+    /// Creates an index from its representation. This may create the None
+    /// value, which is only useful within an `Optioned`.
+    #[inline]
+    pub fn from_repr(repr: Repr) -> Self {
+        Idx {
+            repr,
+            tag: PhantomData,
+        }
+    }
+
+    /// Returns the representation of this index.
+    #[inline]
+    pub fn repr(self) -> Repr {
+        self.repr
+    }
+
+    /// Returns this index as a `usize`.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.repr.index()
+    }
+}
+
+impl<Tag, Repr: Copy> Clone for Idx<Tag, Repr> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Tag, Repr: Copy> Copy for Idx<Tag, Repr> {}
+
+impl<Tag, Repr: PartialEq> PartialEq for Idx<Tag, Repr> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.repr == other.repr
+    }
+}
+
+impl<Tag, Repr: Eq> Eq for Idx<Tag, Repr> {}
+
+impl<Tag, Repr: PartialOrd> PartialOrd for Idx<Tag, Repr> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.repr.partial_cmp(&other.repr)
+    }
+}
+
+impl<Tag, Repr: Ord> Ord for Idx<Tag, Repr> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.repr.cmp(&other.repr)
+    }
+}
+
+impl<Tag, Repr: Hash> Hash for Idx<Tag, Repr> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.repr.hash(state)
+    }
+}
+
+impl<Tag, Repr: Debug> Debug for Idx<Tag, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Idx({:?})", self.repr)
+    }
+}
+
+impl<Tag, Repr: IdxRepr> Noned for Idx<Tag, Repr> {
+    #[inline]
+    fn is_none(&self) -> bool {
+        self.repr.is_none()
+    }
+
+    #[inline]
+    fn get_none() -> Self {
+        Idx::from_repr(Repr::get_none())
+    }
+}
+
+impl<Tag, Repr: IdxRepr> From<Idx<Tag, Repr>> for usize {
+    #[inline]
+    fn from(idx: Idx<Tag, Repr>) -> usize {
+        idx.index()
+    }
+}
+
+/// A `Vec` that is indexed by `Idx<Tag, Repr>` instead of `usize`.
+///
+/// The tag defaults to the element type, so an `IdxVec<Node>` hands out
+/// `Idx<Node>`s.
+pub struct IdxVec<V, Tag = V, Repr = u32> {
+    values: Vec<V>,
+    tag: PhantomData<fn() -> Idx<Tag, Repr>>,
+}
+
+impl<V, Tag, Repr: IdxRepr> IdxVec<V, Tag, Repr> {
+    /// Creates an empty `IdxVec`.
+    #[inline]
+    pub fn new() -> Self {
+        IdxVec::from(Vec::new())
+    }
+
+    /// Creates an empty `IdxVec` with room for `capacity` values.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        IdxVec::from(Vec::with_capacity(capacity))
+    }
+
+    /// Returns the number of values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there are no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Appends a value and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// if the new index cannot be represented as a `Repr`
+    #[inline]
+    pub fn push(&mut self, value: V) -> Idx<Tag, Repr> {
+        let idx = Idx::new(self.values.len());
+        self.values.push(value);
+        idx
+    }
+
+    /// Returns the index the next pushed value will get.
+    #[inline]
+    pub fn next_idx(&self) -> Idx<Tag, Repr> {
+        Idx::new(self.values.len())
+    }
+
+    /// Returns a reference to the value at the index, or `None` if it is
+    /// out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::idx::{Idx, IdxVec};
+    /// let mut names: IdxVec<&str> = IdxVec::new();
+    /// let i = names.push("a");
+    /// assert_eq!(Some(&"a"), names.get(i));
+    /// assert_eq!(None, names.get(Idx::new(1)));
+    /// ```
+    #[inline]
+    pub fn get(&self, idx: Idx<Tag, Repr>) -> Option<&V> {
+        self.values.get(idx.index())
+    }
+
+    /// Returns a mutable reference to the value at the index, or `None` if
+    /// it is out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, idx: Idx<Tag, Repr>) -> Option<&mut V> {
+        self.values.get_mut(idx.index())
+    }
+
+    /// Returns an iterator over the values.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Returns an iterator over the indices and values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::idx::{Idx, IdxVec};
+    /// let v: IdxVec<char> = vec!['a', 'b'].into();
+    /// let pairs: Vec<(Idx<char>, &char)> = v.iter_enumerated().collect();
+    /// assert_eq!(vec![(Idx::new(0), &'a'), (Idx::new(1), &'b')], pairs);
+    /// ```
+    #[inline]
+    pub fn iter_enumerated(&self) -> IterEnumerated<'_, V, Tag, Repr> {
+        IterEnumerated {
+            iter: self.values.iter(),
+            next: 0,
+            tag: PhantomData,
+        }
+    }
+
+    /// Returns the values as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Returns the underlying `Vec`.
+    #[inline]
+    pub fn into_vec(self) -> Vec<V> {
+        self.values
+    }
+}
+
+impl<V, Tag, Repr: IdxRepr> Default for IdxVec<V, Tag, Repr> {
+    #[inline]
+    fn default() -> Self {
+        IdxVec::new()
+    }
+}
+
+impl<V: Clone, Tag, Repr> Clone for IdxVec<V, Tag, Repr> {
+    #[inline]
+    fn clone(&self) -> Self {
+        IdxVec {
+            values: self.values.clone(),
+            tag: PhantomData,
+        }
+    }
+}
+
+impl<V: PartialEq, Tag, Repr> PartialEq for IdxVec<V, Tag, Repr> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<V: Eq, Tag, Repr> Eq for IdxVec<V, Tag, Repr> {}
+
+impl<V: Debug, Tag, Repr> Debug for IdxVec<V, Tag, Repr> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.values.fmt(f)
+    }
+}
+
+impl<V, Tag, Repr: IdxRepr> From<Vec<V>> for IdxVec<V, Tag, Repr> {
+    /// # Panics
+    ///
+    /// if the last index cannot be represented as a `Repr`
+    ///
+    /// ```should_panic
+    ///# use optional::idx::IdxVec;
+    /// // index 255 is the None value of `u8`
+    /// IdxVec::<char, char, u8>::from(vec!['x'; 256]);
+    /// ```
+    #[inline]
+    fn from(values: Vec<V>) -> Self {
+        assert!(
+            values.is_empty() || Repr::from_index(values.len() - 1).is_some(),
+            "{} values are too many for the index type",
+            values.len()
+        );
+        IdxVec {
+            values,
+            tag: PhantomData,
+        }
+    }
+}
+
+impl<V, Tag, Repr: IdxRepr> Index<Idx<Tag, Repr>> for IdxVec<V, Tag, Repr> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, idx: Idx<Tag, Repr>) -> &V {
+        &self.values[idx.index()]
+    }
+}
+
+impl<V, Tag, Repr: IdxRepr> IndexMut<Idx<Tag, Repr>> for IdxVec<V, Tag, Repr> {
+    #[inline]
+    fn index_mut(&mut self, idx: Idx<Tag, Repr>) -> &mut V {
+        &mut self.values[idx.index()]
+    }
+}
+
+impl<'a, V, Tag, Repr: IdxRepr> IntoIterator for &'a IdxVec<V, Tag, Repr> {
+    type Item = &'a V;
+    type IntoIter = Iter<'a, V>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a, V> {
+        self.values.iter()
+    }
+}
+
+impl<V, Tag, Repr: IdxRepr> FromIterator<V> for IdxVec<V, Tag, Repr> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        IdxVec::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+/// Iterator over the indices and values of an `IdxVec`
+pub struct IterEnumerated<'a, V: 'a, Tag, Repr> {
+    iter: Iter<'a, V>,
+    next: usize,
+    tag: PhantomData<fn() -> Idx<Tag, Repr>>,
+}
+
+impl<'a, V, Tag, Repr: IdxRepr> Iterator for IterEnumerated<'a, V, Tag, Repr> {
+    type Item = (Idx<Tag, Repr>, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        self.next += 1;
+        Some((Idx::new(self.next - 1), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
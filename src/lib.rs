@@ -53,6 +53,7 @@
 //! your type, provided that your type is already Copy and Sized.
 //!
 
+#![cfg_attr(feature = "unstable", feature(try_trait_v2))]
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 
@@ -61,14 +62,32 @@ extern crate serde;
 
 use self::OptionBool::*;
 use std::cmp::Ordering;
-use std::convert::From;
+use std::convert::{From, TryFrom};
 use std::fmt::{self, Debug, Error};
 use std::hash::{Hash, Hasher};
 use std::iter::Iterator;
 use std::mem;
-use std::ops::{Deref, Index, RangeFull};
+use std::ops::{Deref, DerefMut, Index, RangeFull};
 use std::slice::Iter;
 
+pub mod array;
+pub mod atomic;
+pub mod cell;
+pub mod display;
+pub mod fill;
+pub mod idx;
+pub mod map;
+pub mod once;
+pub mod option_like;
+pub mod policy;
+pub mod question_mark;
+pub mod range;
+pub mod runs;
+pub mod slab;
+pub mod slice;
+pub mod sort;
+pub mod vec;
+
 /// The `OptionBool` type, a space-efficient Option<bool> replacement
 #[derive(Copy, Clone, PartialEq, Eq, Ord, Hash)]
 pub enum OptionBool {
@@ -106,6 +125,50 @@ impl Deref for OptionBool {
     }
 }
 
+/// # Examples
+///
+/// ```
+///# use optional::OptionBool;
+/// assert!(OptionBool::SomeTrue == true);
+/// assert!(OptionBool::None != false);
+/// assert!(false == OptionBool::SomeFalse);
+/// ```
+impl PartialEq<bool> for OptionBool {
+    #[inline]
+    fn eq(&self, other: &bool) -> bool {
+        *self == OptionBool::some(*other)
+    }
+}
+
+impl PartialEq<OptionBool> for bool {
+    #[inline]
+    fn eq(&self, other: &OptionBool) -> bool {
+        other == self
+    }
+}
+
+/// # Examples
+///
+/// ```
+///# use optional::OptionBool;
+/// assert!(OptionBool::SomeTrue == Some(true));
+/// assert!(OptionBool::None == None);
+/// assert!(Some(false) != OptionBool::None);
+/// ```
+impl PartialEq<Option<bool>> for OptionBool {
+    #[inline]
+    fn eq(&self, other: &Option<bool>) -> bool {
+        *self == OptionBool::from(*other)
+    }
+}
+
+impl PartialEq<OptionBool> for Option<bool> {
+    #[inline]
+    fn eq(&self, other: &OptionBool) -> bool {
+        other == self
+    }
+}
+
 impl<'a> PartialEq<OptionBool> for &'a OptionBool {
     #[inline]
     fn eq(&self, other: &OptionBool) -> bool {
@@ -184,7 +247,7 @@ impl serde::Serialize for OptionBool {
     ///# extern crate optional;
     ///# use optional::OptionBool::SomeTrue;
     ///# fn main() {
-    /// assert_eq!(SomeTrue, serde_json::from_str("true").unwrap());
+    /// assert_eq!(SomeTrue, serde_json::from_str::<optional::OptionBool>("true").unwrap());
     ///# }
     /// ```
     #[inline]
@@ -906,6 +969,303 @@ impl OptionBool {
             SomeFalse
         })
     }
+
+    /// Returns `true` if the option contains a value that matches the
+    /// predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// assert!(OptionBool::SomeTrue.is_some_and(|b| b));
+    /// assert!(!OptionBool::SomeFalse.is_some_and(|b| b));
+    /// assert!(!OptionBool::None.is_some_and(|b| b));
+    /// ```
+    #[inline]
+    pub fn is_some_and<F>(self, f: F) -> bool
+    where
+        F: FnOnce(bool) -> bool,
+    {
+        match self {
+            SomeTrue => f(true),
+            SomeFalse => f(false),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the option is `None` or contains a value that
+    /// matches the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// assert!(OptionBool::SomeTrue.is_none_or(|b| b));
+    /// assert!(!OptionBool::SomeFalse.is_none_or(|b| b));
+    /// assert!(OptionBool::None.is_none_or(|b| b));
+    /// ```
+    #[inline]
+    pub fn is_none_or<F>(self, f: F) -> bool
+    where
+        F: FnOnce(bool) -> bool,
+    {
+        match self {
+            SomeTrue => f(true),
+            SomeFalse => f(false),
+            None => true,
+        }
+    }
+
+    /// Calls `f` with the contained value, if any, and returns the option
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// let mut seen = vec![];
+    /// let x = OptionBool::SomeFalse.inspect(|b| seen.push(*b));
+    /// OptionBool::None.inspect(|b| seen.push(*b));
+    /// assert_eq!(OptionBool::SomeFalse, x);
+    /// assert_eq!(vec![false], seen);
+    /// ```
+    #[inline]
+    pub fn inspect<F>(self, f: F) -> OptionBool
+    where
+        F: FnOnce(&bool),
+    {
+        match self {
+            SomeTrue => f(&true),
+            SomeFalse => f(&false),
+            None => (),
+        }
+        self
+    }
+
+    /// Inserts `value` into the option and returns it. Unlike
+    /// `Option::insert`, this returns the value rather than a reference,
+    /// since an `OptionBool` doesn't contain a `bool`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// let mut x = OptionBool::None;
+    /// assert!(x.insert(true));
+    /// assert_eq!(OptionBool::SomeTrue, x);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: bool) -> bool {
+        *self = OptionBool::some(value);
+        value
+    }
+
+    /// Inserts `value` if the option is `None`, then returns the contained
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// let mut x = OptionBool::None;
+    /// assert!(x.get_or_insert(true));
+    /// assert!(x.get_or_insert(false));
+    /// assert_eq!(OptionBool::SomeTrue, x);
+    /// ```
+    #[inline]
+    pub fn get_or_insert(&mut self, value: bool) -> bool {
+        self.get_or_insert_with(|| value)
+    }
+
+    /// Inserts `false` if the option is `None`, then returns the contained
+    /// value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// let mut x = OptionBool::None;
+    /// assert!(!x.get_or_insert_default());
+    /// assert_eq!(OptionBool::SomeFalse, x);
+    /// ```
+    #[inline]
+    pub fn get_or_insert_default(&mut self) -> bool {
+        self.get_or_insert_with(bool::default)
+    }
+
+    /// Inserts the result of `f` if the option is `None`, then returns the
+    /// contained value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// let mut x = OptionBool::SomeFalse;
+    /// assert!(!x.get_or_insert_with(|| unreachable!()));
+    /// ```
+    #[inline]
+    pub fn get_or_insert_with<F>(&mut self, f: F) -> bool
+    where
+        F: FnOnce() -> bool,
+    {
+        match *self {
+            SomeTrue => true,
+            SomeFalse => false,
+            None => self.insert(f()),
+        }
+    }
+
+    /// Takes the value out of the option if the predicate returns `true`
+    /// for it, leaving `None` in its place, and returns it as an
+    /// `Option<bool>`.
+    ///
+    /// Note that there is also [`take_if_bool(..)`](#method.take_if_bool)
+    /// which works similarly, but returns an `OptionBool`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// let mut x = OptionBool::SomeTrue;
+    /// assert_eq!(None, x.take_if(|b| !b));
+    /// assert_eq!(Some(true), x.take_if(|b| b));
+    /// assert_eq!(OptionBool::None, x);
+    /// ```
+    #[inline]
+    pub fn take_if<P>(&mut self, predicate: P) -> Option<bool>
+    where
+        P: FnOnce(bool) -> bool,
+    {
+        self.take_if_bool(predicate).into()
+    }
+
+    /// Takes the value out of the option if the predicate returns `true`
+    /// for it, leaving `None` in its place.
+    ///
+    /// Note that there is also [`take_if(..)`](#method.take_if) which works
+    /// similarly, but returns an `Option<bool>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// let mut x = OptionBool::SomeFalse;
+    /// assert_eq!(OptionBool::None, x.take_if_bool(|b| b));
+    /// assert_eq!(OptionBool::SomeFalse, x.take_if_bool(|b| !b));
+    /// assert_eq!(OptionBool::None, x);
+    /// ```
+    #[inline]
+    pub fn take_if_bool<P>(&mut self, predicate: P) -> OptionBool
+    where
+        P: FnOnce(bool) -> bool,
+    {
+        if self.is_some_and(predicate) {
+            self.take_bool()
+        } else {
+            None
+        }
+    }
+
+    /// Zips this option with an `Option<U>`. Returns `Some((b, u))` if both
+    /// contain values, otherwise `None`.
+    ///
+    /// To zip with an `OptionBool`, convert it with `.into()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// assert_eq!(Some((true, 'x')), OptionBool::SomeTrue.zip(Some('x')));
+    /// assert_eq!(None, OptionBool::SomeTrue.zip(None::<char>));
+    /// assert_eq!(None, OptionBool::None.zip(Some('x')));
+    /// assert_eq!(Some((true, false)), OptionBool::SomeTrue.zip(OptionBool::SomeFalse.into()));
+    /// ```
+    #[inline]
+    pub fn zip<U>(self, other: Option<U>) -> Option<(bool, U)> {
+        Option::<bool>::from(self).zip(other)
+    }
+
+    /// Combines this option with another `OptionBool` using `f`. Returns
+    /// `Some(f(a, b))` as an `OptionBool` if both contain values, otherwise
+    /// `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// let eq = |a, b| a == b;
+    /// assert_eq!(OptionBool::SomeFalse, OptionBool::SomeTrue.zip_with_bool(OptionBool::SomeFalse, eq));
+    /// assert_eq!(OptionBool::None, OptionBool::None.zip_with_bool(OptionBool::SomeFalse, eq));
+    /// ```
+    #[inline]
+    pub fn zip_with_bool<F>(self, other: OptionBool, f: F) -> OptionBool
+    where
+        F: FnOnce(bool, bool) -> bool,
+    {
+        self.zip(other.into()).map(|(a, b)| f(a, b)).into()
+    }
+
+    /// Returns a guard to change the contained value in place, or `None` if
+    /// there is no value. The new value is written back when the guard is
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::OptionBool;
+    /// let mut x = OptionBool::SomeTrue;
+    /// if let Some(mut b) = x.as_mut() {
+    ///     *b = !*b;
+    /// }
+    /// assert_eq!(OptionBool::SomeFalse, x);
+    /// assert!(OptionBool::None.as_mut().is_none());
+    /// ```
+    #[inline]
+    pub fn as_mut(&mut self) -> Option<OptionBoolMut<'_>> {
+        let value = match *self {
+            SomeTrue => true,
+            SomeFalse => false,
+            None => return Option::None,
+        };
+        Option::Some(OptionBoolMut { o: self, value })
+    }
+}
+
+/// A guard for changing the value of an `OptionBool` in place, returned by
+/// [`OptionBool::as_mut`](enum.OptionBool.html#method.as_mut). The value is
+/// written back when the guard is dropped.
+pub struct OptionBoolMut<'a> {
+    o: &'a mut OptionBool,
+    value: bool,
+}
+
+impl<'a> Deref for OptionBoolMut<'a> {
+    type Target = bool;
+
+    #[inline]
+    fn deref(&self) -> &bool {
+        &self.value
+    }
+}
+
+impl<'a> DerefMut for OptionBoolMut<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut bool {
+        &mut self.value
+    }
+}
+
+impl<'a> Drop for OptionBoolMut<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        *self.o = OptionBool::some(self.value);
+    }
+}
+
+impl<'a> Debug for OptionBoolMut<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        self.value.fmt(f)
+    }
 }
 
 impl Debug for OptionBool {
@@ -988,6 +1348,42 @@ impl<'a> From<&'a OptionBool> for Option<bool> {
     }
 }
 
+/// # Examples
+///
+/// ```
+///# use optional::OptionBool;
+/// assert_eq!(OptionBool::SomeTrue, OptionBool::from(true));
+/// let b: OptionBool = false.into();
+/// assert_eq!(OptionBool::SomeFalse, b);
+/// ```
+impl From<bool> for OptionBool {
+    #[inline]
+    fn from(b: bool) -> Self {
+        OptionBool::some(b)
+    }
+}
+
+/// # Examples
+///
+/// ```
+///# use optional::OptionBool;
+/// fn describe<O: AsRef<Option<bool>>>(o: O) -> &'static str {
+///     match *o.as_ref() {
+///         Some(true) => "yes",
+///         Some(false) => "no",
+///         None => "unknown",
+///     }
+/// }
+/// assert_eq!("unknown", describe(OptionBool::None));
+/// assert_eq!("yes", describe(OptionBool::SomeTrue));
+/// ```
+impl AsRef<Option<bool>> for OptionBool {
+    #[inline]
+    fn as_ref(&self) -> &Option<bool> {
+        self
+    }
+}
+
 impl From<Option<bool>> for OptionBool {
     #[inline]
     fn from(o: Option<bool>) -> Self {
@@ -1018,6 +1414,17 @@ pub trait Noned {
     fn is_none(&self) -> bool;
     /// Returns the declared `None` value for `T`.
     fn get_none() -> Self;
+
+    /// Returns the value nearest to the `None` value that is not `None`
+    /// itself, if there is one. This is what `CollisionPolicy::Saturate`
+    /// uses; the default returns `None`.
+    #[inline]
+    fn nearest_value() -> Option<Self>
+    where
+        Self: Sized,
+    {
+        Option::None
+    }
 }
 
 impl Noned for u8 {
@@ -1030,6 +1437,11 @@ impl Noned for u8 {
     fn get_none() -> u8 {
         std::u8::MAX
     }
+
+    #[inline]
+    fn nearest_value() -> Option<u8> {
+        Some(std::u8::MAX - 1)
+    }
 }
 
 impl Noned for u16 {
@@ -1042,6 +1454,11 @@ impl Noned for u16 {
     fn get_none() -> u16 {
         std::u16::MAX
     }
+
+    #[inline]
+    fn nearest_value() -> Option<u16> {
+        Some(std::u16::MAX - 1)
+    }
 }
 
 impl Noned for u32 {
@@ -1054,6 +1471,11 @@ impl Noned for u32 {
     fn get_none() -> u32 {
         std::u32::MAX
     }
+
+    #[inline]
+    fn nearest_value() -> Option<u32> {
+        Some(std::u32::MAX - 1)
+    }
 }
 
 impl Noned for u64 {
@@ -1066,6 +1488,11 @@ impl Noned for u64 {
     fn get_none() -> u64 {
         std::u64::MAX
     }
+
+    #[inline]
+    fn nearest_value() -> Option<u64> {
+        Some(std::u64::MAX - 1)
+    }
 }
 
 impl Noned for usize {
@@ -1078,6 +1505,11 @@ impl Noned for usize {
     fn get_none() -> usize {
         std::usize::MAX
     }
+
+    #[inline]
+    fn nearest_value() -> Option<usize> {
+        Some(std::usize::MAX - 1)
+    }
 }
 
 impl Noned for i8 {
@@ -1090,6 +1522,11 @@ impl Noned for i8 {
     fn get_none() -> i8 {
         std::i8::MIN
     }
+
+    #[inline]
+    fn nearest_value() -> Option<i8> {
+        Some(std::i8::MIN + 1)
+    }
 }
 
 impl Noned for i16 {
@@ -1102,6 +1539,11 @@ impl Noned for i16 {
     fn get_none() -> i16 {
         std::i16::MIN
     }
+
+    #[inline]
+    fn nearest_value() -> Option<i16> {
+        Some(std::i16::MIN + 1)
+    }
 }
 
 impl Noned for i32 {
@@ -1114,6 +1556,11 @@ impl Noned for i32 {
     fn get_none() -> i32 {
         std::i32::MIN
     }
+
+    #[inline]
+    fn nearest_value() -> Option<i32> {
+        Some(std::i32::MIN + 1)
+    }
 }
 
 impl Noned for i64 {
@@ -1126,6 +1573,11 @@ impl Noned for i64 {
     fn get_none() -> i64 {
         std::i64::MIN
     }
+
+    #[inline]
+    fn nearest_value() -> Option<i64> {
+        Some(std::i64::MIN + 1)
+    }
 }
 
 impl Noned for isize {
@@ -1138,6 +1590,11 @@ impl Noned for isize {
     fn get_none() -> isize {
         std::isize::MIN
     }
+
+    #[inline]
+    fn nearest_value() -> Option<isize> {
+        Some(std::isize::MIN + 1)
+    }
 }
 
 impl Noned for f32 {
@@ -1174,99 +1631,102 @@ impl Noned for char {
     fn get_none() -> char {
         '\0'
     }
-}
-
-///Equality within Optioned
-pub trait OptEq {
-    /// Is the other optioned equal to this one?
-    fn opt_eq(&self, other: &Self) -> bool;
-}
 
-impl OptEq for u8 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
-    }
-}
-impl OptEq for u16 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
-    }
-}
-impl OptEq for u32 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
-    }
-}
-impl OptEq for u64 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
-    }
-}
-impl OptEq for usize {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
+    #[inline]
+    fn nearest_value() -> Option<char> {
+        Some('\u{1}')
     }
 }
 
-impl OptEq for i8 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
-    }
-}
-impl OptEq for i16 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
-    }
-}
-impl OptEq for i32 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
-    }
-}
-impl OptEq for i64 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
-    }
-}
-impl OptEq for isize {
-    fn opt_eq(&self, other: &Self) -> bool {
-        self == other
+/// `OptionBool::None` is the None value, so an `Optioned<OptionBool>` is
+/// the same as an `OptionBool`.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none, wrap, OptionBool};
+/// assert_eq!(none(), wrap(OptionBool::None));
+/// assert_eq!(some(OptionBool::SomeTrue), wrap(OptionBool::SomeTrue));
+/// ```
+impl Noned for OptionBool {
+    #[inline]
+    fn is_none(&self) -> bool {
+        *self == None
     }
-}
 
-impl OptEq for f32 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        if self.is_nan() {
-            other.is_nan()
-        } else {
-            self == other
-        }
+    #[inline]
+    fn get_none() -> OptionBool {
+        None
     }
 }
-impl OptEq for f64 {
-    fn opt_eq(&self, other: &Self) -> bool {
-        if self.is_nan() {
-            other.is_nan()
-        } else {
-            self == other
-        }
-    }
+
+///Equality within Optioned
+///
+/// This is implemented for every `Noned` type that is `PartialEq`, using
+/// [`opt_eq`](fn.opt_eq.html), so that all None values (e.g. all NaNs) are
+/// equal.
+pub trait OptEq {
+    /// Is the other optioned equal to this one?
+    fn opt_eq(&self, other: &Self) -> bool;
 }
 
-impl OptEq for char {
+impl<T: Noned + PartialEq> OptEq for T {
+    #[inline]
     fn opt_eq(&self, other: &Self) -> bool {
-        self == other
+        opt_eq(self, other)
     }
 }
 
 ///Ordering within Optioned
+///
+/// This is implemented for every `Noned` type that is `PartialOrd`, using
+/// [`opt_cmp_partial`](fn.opt_cmp_partial.html), so that None values come
+/// first.
 pub trait OptOrd {
     /// compare this Optioned with another
     fn opt_cmp(&self, other: &Self) -> Ordering;
 }
 
+impl<T: Noned + PartialOrd> OptOrd for T {
+    #[inline]
+    fn opt_cmp(&self, other: &Self) -> Ordering {
+        opt_cmp_partial(self, other)
+    }
+}
+
+/// Compares two values for equality, treating all None values as equal to
+/// each other and unequal to everything else.
+///
+/// # Examples
+///
+/// ```
+///# use optional::opt_eq;
+/// assert!(opt_eq(&std::f32::NAN, &-std::f32::NAN));
+/// assert!(!opt_eq(&std::f32::NAN, &1.0));
+/// assert!(opt_eq(&1.0f32, &1.0));
+/// ```
+#[inline]
+pub fn opt_eq<T: Noned + PartialEq>(a: &T, b: &T) -> bool {
+    if a.is_none() {
+        b.is_none()
+    } else {
+        a == b
+    }
+}
+
+/// Compares two values, placing None values before all others.
+///
+/// # Examples
+///
+/// ```
+///# use optional::opt_cmp;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less, opt_cmp(&std::u8::MAX, &0));
+/// assert_eq!(Ordering::Greater, opt_cmp(&2u8, &1));
+/// ```
 #[inline]
-fn _opt_cmp<T: Ord + Copy + Noned>(a: &T, b: &T) -> Ordering {
+pub fn opt_cmp<T: Noned + Ord>(a: &T, b: &T) -> Ordering {
     if a.is_none() {
         if b.is_none() {
             Ordering::Equal
@@ -1280,8 +1740,24 @@ fn _opt_cmp<T: Ord + Copy + Noned>(a: &T, b: &T) -> Ordering {
     }
 }
 
+/// Compares two values that are only `PartialOrd`, placing None values
+/// before all others.
+///
+/// # Panics
+///
+/// if two values that are not None cannot be compared
+///
+/// # Examples
+///
+/// ```
+///# use optional::opt_cmp_partial;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(Ordering::Less, opt_cmp_partial(&std::f64::NAN, &std::f64::NEG_INFINITY));
+/// assert_eq!(Ordering::Equal, opt_cmp_partial(&std::f64::NAN, &std::f64::NAN));
+/// ```
 #[inline]
-fn _opt_cmp_part<T: PartialOrd + Copy + Noned>(a: &T, b: &T) -> Ordering {
+pub fn opt_cmp_partial<T: Noned + PartialOrd>(a: &T, b: &T) -> Ordering {
     if a.is_none() {
         if b.is_none() {
             Ordering::Equal
@@ -1295,75 +1771,6 @@ fn _opt_cmp_part<T: PartialOrd + Copy + Noned>(a: &T, b: &T) -> Ordering {
     }
 }
 
-impl OptOrd for u8 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        _opt_cmp(self, other)
-    }
-}
-impl OptOrd for u16 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        _opt_cmp(self, other)
-    }
-}
-impl OptOrd for u32 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        _opt_cmp(self, other)
-    }
-}
-impl OptOrd for u64 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        _opt_cmp(self, other)
-    }
-}
-impl OptOrd for usize {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        _opt_cmp(self, other)
-    }
-}
-
-impl OptOrd for i8 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        self.cmp(other)
-    }
-}
-impl OptOrd for i16 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        self.cmp(other)
-    }
-}
-impl OptOrd for i32 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        self.cmp(other)
-    }
-}
-impl OptOrd for i64 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        self.cmp(other)
-    }
-}
-impl OptOrd for isize {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        self.cmp(other)
-    }
-}
-
-impl OptOrd for f32 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        _opt_cmp_part(self, other)
-    }
-}
-impl OptOrd for f64 {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        _opt_cmp_part(self, other)
-    }
-}
-
-impl OptOrd for char {
-    fn opt_cmp(&self, other: &Self) -> Ordering {
-        _opt_cmp(self, other)
-    }
-}
-
 /// An `Option<T>`-like structure that takes only as much space as the enclosed
 /// value, at the cost of removing one particular `None` value from the value
 /// domain (see `Noned`)
@@ -1413,6 +1820,112 @@ where
     }
 }
 
+/// An `Optioned` equals a plain value like an `Option` equals `Some` of
+/// it, so `None` equals no value, not even the None value.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+/// assert!(some(5u8) == 5);
+/// assert!(none::<u8>() != 5);
+/// assert!(none::<u8>() != 255);
+/// ```
+impl<T> PartialEq<T> for Optioned<T>
+where
+    T: OptEq + Noned + Copy,
+{
+    #[inline]
+    fn eq(&self, other: &T) -> bool {
+        self.is_some() && self.value.opt_eq(other)
+    }
+}
+
+/// An `Optioned` equals an `Option` if both are `None` or both contain
+/// equal values. `Some` of the None value equals nothing.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+/// assert!(some(5u8) == Some(5));
+/// assert!(none::<u8>() == None);
+/// assert!(Some(1.5f32) == some(1.5));
+/// assert!(none::<u8>() != Some(255));
+/// ```
+impl<T> PartialEq<Option<T>> for Optioned<T>
+where
+    T: OptEq + Noned + Copy,
+{
+    #[inline]
+    fn eq(&self, other: &Option<T>) -> bool {
+        match *other {
+            Option::Some(ref v) => self.is_some() && self.value.opt_eq(v),
+            Option::None => self.is_none(),
+        }
+    }
+}
+
+impl<T> PartialEq<Optioned<T>> for Option<T>
+where
+    T: OptEq + Noned + Copy,
+{
+    #[inline]
+    fn eq(&self, other: &Optioned<T>) -> bool {
+        other == self
+    }
+}
+
+/// Compares like an `Option` with `Some` of the plain value, so `None` is
+/// less than any value. The None value cannot be compared.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+/// assert!(some(5i32) > 3);
+/// assert!(none::<i32>() < std::i32::MAX);
+/// assert_eq!(None, none::<i32>().partial_cmp(&std::i32::MIN));
+/// ```
+impl<T> PartialOrd<T> for Optioned<T>
+where
+    T: OptEq + OptOrd + Noned + Copy,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        self.partial_cmp(&Some(*other))
+    }
+}
+
+/// Compares like two `Option`s, so `None` is less than any value. `Some` of
+/// the None value cannot be compared.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+/// assert!(some(1u16) > None);
+/// assert!(none::<u16>() < Some(0));
+/// assert!(some(2.0f64) <= Some(2.0));
+/// ```
+impl<T> PartialOrd<Option<T>> for Optioned<T>
+where
+    T: OptEq + OptOrd + Noned + Copy,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Option<T>) -> Option<Ordering> {
+        match *other {
+            Option::Some(ref v) if v.is_none() => Option::None,
+            Option::Some(ref v) => Some(self.value.opt_cmp(v)),
+            Option::None => Some(if self.is_none() {
+                Ordering::Equal
+            } else {
+                Ordering::Greater
+            }),
+        }
+    }
+}
+
 impl<T> Hash for Optioned<T>
 where
     T: Noned + Copy + Hash,
@@ -1461,7 +1974,7 @@ where
     ///# extern crate optional;
     ///# use optional::{Optioned, Noned, some};
     ///# fn main() {
-    /// assert_eq!(some(1f32), serde_json::from_str("1.0").unwrap());
+    /// assert_eq!(some(1f32), serde_json::from_str::<Optioned<f32>>("1.0").unwrap());
     ///# }
     /// ```
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -1502,6 +2015,41 @@ impl<T: Noned + Copy> Optioned<T> {
         Optioned::<T> { value: t }
     }
 
+    /// Create an `Optioned<T>` that is `some(t)`, or return an error if the
+    /// supplied value is the None value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{Optioned, some};
+    /// assert_eq!(Ok(some(1u8)), Optioned::try_some(1u8));
+    /// assert_eq!(255, Optioned::try_some(255u8).unwrap_err().value);
+    /// ```
+    #[inline]
+    pub fn try_some(t: T) -> Result<Self, SentinelError<T>> {
+        if t.is_none() {
+            Err(SentinelError { value: t })
+        } else {
+            Ok(Optioned { value: t })
+        }
+    }
+
+    /// Convert an `Option<T>` into an `Optioned<T>`, or return an error if it
+    /// contains the None value. Unlike `From<Option<T>>`, this never panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{Optioned, some, none};
+    /// assert_eq!(Ok(some('a')), Optioned::try_from_option(Some('a')));
+    /// assert_eq!(Ok(none()), Optioned::<char>::try_from_option(None));
+    /// assert!(Optioned::try_from_option(Some('\0')).is_err());
+    /// ```
+    #[inline]
+    pub fn try_from_option(o: Option<T>) -> Result<Self, SentinelError<T>> {
+        o.map_or_else(|| Ok(none()), Optioned::try_some)
+    }
+
     /// Create an `Optioned<T>` that is `none()`.
     ///
     /// # Examples
@@ -1953,6 +2501,25 @@ impl<T: Noned + Copy> Optioned<T> {
         }
     }
 
+    /// Transforms the `Optioned<T>` into a `Result<T, NoneError>`, mapping
+    /// `some(v)` to `Ok(v)` and `none()` to `Err(NoneError)`, so the `None`
+    /// can be propagated with `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{Optioned, NoneError, some, none};
+    /// fn double(x: Optioned<u16>) -> Result<u16, NoneError> {
+    ///     Ok(x.into_result()? * 2)
+    /// }
+    /// assert_eq!(Ok(4), double(some(2)));
+    /// assert_eq!(Err(NoneError), double(none()));
+    /// ```
+    #[inline]
+    pub fn into_result(self) -> Result<T, NoneError> {
+        self.ok_or(NoneError)
+    }
+
     /// Transforms the `Optioned<T>` into a `Result<T, E>`, mapping `some(v)` to
     /// `Ok(v)` and `none()` to `Err(err)`.
     ///
@@ -2057,56 +2624,483 @@ impl<T: Noned + Copy> Optioned<T> {
     pub fn replace(&mut self, value: T) -> Self {
         mem::replace(self, some(value))
     }
-}
 
-impl<T: Noned + Copy + Clone + Default> Optioned<T> {
-    /// Returns the contained value or a default
-    ///
-    /// Consumes the `self` argument then, if it [`is_some`], returns the
-    /// contained value, otherwise if none, returns the default value for
-    /// that type as per the `Default` trait.
-    ///
-    /// [`is_some`]: struct.Optioned.html#method.is_some
+    /// Returns `true` if the `Optioned` contains a value that matches the
+    /// predicate.
     ///
-    /// # Examples:
+    /// # Examples
     ///
     /// ```
     ///# use optional::{some, none};
-    /// assert_eq!(1u8, some(1).unwrap_or_default());
-    /// assert_eq!(0u32, none().unwrap_or_default());
+    /// assert_eq!(some(2u32).is_some_and(|x| x > 1), true);
+    /// assert_eq!(some(0u32).is_some_and(|x| x > 1), false);
+    /// assert_eq!(none::<u32>().is_some_and(|x| x > 1), false);
     /// ```
     #[inline]
-    pub fn unwrap_or_default(self) -> T {
-        if self.is_some() {
-            self.value
-        } else {
-            Default::default()
-        }
+    pub fn is_some_and<F>(self, f: F) -> bool
+    where
+        F: FnOnce(T) -> bool,
+    {
+        self.is_some() && f(self.value)
     }
-}
 
-impl<T: Noned + Copy + PartialEq> Optioned<T> {
-    /// Returns `true' if this `Optioned` contains the given value,
-    /// `false` otherwise.
+    /// Returns `true` if the `Optioned` is `None` or contains a value that
+    /// matches the predicate.
     ///
     /// # Examples
     ///
     /// ```
     ///# use optional::{some, none};
-    /// assert!(some(42u8).contains(42));
-    /// assert!(!some(1u32).contains(12));
-    /// assert!(!none().contains(0usize));
+    /// assert_eq!(some(2u32).is_none_or(|x| x > 1), true);
+    /// assert_eq!(some(0u32).is_none_or(|x| x > 1), false);
+    /// assert_eq!(none::<u32>().is_none_or(|x| x > 1), true);
     /// ```
     #[inline]
-    pub fn contains(&self, value: T) -> bool {
-        self.is_some() && self.value == value
+    pub fn is_none_or<F>(self, f: F) -> bool
+    where
+        F: FnOnce(T) -> bool,
+    {
+        self.is_none() || f(self.value)
     }
-}
 
-/// Create an `Optioned<T>` that is `some(t)`.
-///
-/// # Panics
-///
+    /// Returns a reference to the contained value, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// let text = some('x');
+    /// assert_eq!(Some(&'x'), text.as_ref());
+    /// assert_eq!(None, none::<char>().as_ref());
+    /// ```
+    #[inline]
+    pub fn as_ref(&self) -> Option<&T> {
+        if self.is_none() {
+            Option::None
+        } else {
+            Option::Some(&self.value)
+        }
+    }
+
+    /// Calls `f` with the contained value, if any, and returns the
+    /// `Optioned` unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// let mut seen = vec![];
+    /// let x = some(4u8).inspect(|x| seen.push(*x));
+    /// none::<u8>().inspect(|x| seen.push(*x));
+    /// assert_eq!(some(4), x);
+    /// assert_eq!(vec![4], seen);
+    /// ```
+    #[inline]
+    pub fn inspect<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&T),
+    {
+        if self.is_some() {
+            f(&self.value);
+        }
+        self
+    }
+
+    /// Returns `None` if the `Optioned` is none, otherwise calls `f` with
+    /// the contained value and returns the resulting `Option`. Use this
+    /// instead of `and_then` if the result cannot be an `Optioned`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// fn sq_then_to_string(x: u32) -> Option<String> {
+    ///     x.checked_mul(x).map(|sq| sq.to_string())
+    /// }
+    ///
+    /// assert_eq!(some(2).and_then_option(sq_then_to_string), Some(4.to_string()));
+    /// assert_eq!(some(1_000_000).and_then_option(sq_then_to_string), None); // overflowed!
+    /// assert_eq!(none().and_then_option(sq_then_to_string), None);
+    /// ```
+    #[inline]
+    pub fn and_then_option<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(T) -> Option<U>,
+    {
+        if self.is_some() {
+            f(self.value)
+        } else {
+            Option::None
+        }
+    }
+
+    /// Inserts `value` into the `Optioned`, then returns a guard to change
+    /// it in place, like [`as_mut`](#method.as_mut).
+    ///
+    /// # Panics
+    ///
+    /// if `value` is the None value, or if the None value is written
+    /// through the guard
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none, Optioned};
+    /// let mut opt: Optioned<u32> = none();
+    /// {
+    ///     let mut val = opt.insert(1);
+    ///     assert_eq!(*val, 1);
+    ///     *val = 3;
+    /// }
+    /// assert_eq!(opt, some(3));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: T) -> OptionedMut<'_, T> {
+        *self = some(value);
+        self._guard()
+    }
+
+    /// Inserts `value` if the `Optioned` is `None`, then returns a guard to
+    /// change the contained value in place, like [`as_mut`](#method.as_mut).
+    ///
+    /// # Panics
+    ///
+    /// if `value` is inserted and is the None value, or if the None value
+    /// is written through the guard
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none, Optioned};
+    /// let mut x: Optioned<u32> = none();
+    /// {
+    ///     let mut y = x.get_or_insert(5);
+    ///     assert_eq!(*y, 5);
+    ///     *y = 7;
+    /// }
+    /// assert_eq!(x, some(7));
+    /// ```
+    ///
+    /// ```should_panic
+    ///# use optional::some;
+    /// let mut x = some(2u8);
+    /// *x.get_or_insert(5) = 255; // panics when the guard is dropped
+    /// ```
+    #[inline]
+    pub fn get_or_insert(&mut self, value: T) -> OptionedMut<'_, T> {
+        if self.is_none() {
+            *self = some(value);
+        }
+        self._guard()
+    }
+
+    /// Inserts the result of `f` if the `Optioned` is `None`, then returns a
+    /// guard to change the contained value in place, like
+    /// [`as_mut`](#method.as_mut).
+    ///
+    /// # Panics
+    ///
+    /// if `f` is called and returns the None value, or if the None value is
+    /// written through the guard
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none, Optioned};
+    /// let mut x: Optioned<u32> = none();
+    /// {
+    ///     let mut y = x.get_or_insert_with(|| 5);
+    ///     assert_eq!(*y, 5);
+    ///     *y = 7;
+    /// }
+    /// assert_eq!(x, some(7));
+    /// ```
+    #[inline]
+    pub fn get_or_insert_with<F>(&mut self, f: F) -> OptionedMut<'_, T>
+    where
+        F: FnOnce() -> T,
+    {
+        if self.is_none() {
+            *self = some(f());
+        }
+        self._guard()
+    }
+
+    // a guard for the contained value, which must not be None
+    #[inline]
+    fn _guard(&mut self) -> OptionedMut<'_, T> {
+        OptionedMut {
+            value: &mut self.value,
+            policy: NoneWritePolicy::Panic,
+        }
+    }
+
+    /// Takes the value out of the `Optioned` if the predicate returns `true`
+    /// for it, leaving `None` in its place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// let mut x = some(42u32);
+    /// assert_eq!(None, x.take_if(|v| v % 2 == 1));
+    /// assert_eq!(some(42), x);
+    /// assert_eq!(Some(42), x.take_if(|v| v == 42));
+    /// assert_eq!(none(), x);
+    /// ```
+    #[inline]
+    pub fn take_if<P>(&mut self, predicate: P) -> Option<T>
+    where
+        P: FnOnce(T) -> bool,
+    {
+        if self.is_some_and(predicate) {
+            self.take()
+        } else {
+            Option::None
+        }
+    }
+
+    /// Zips this `Optioned` with another. Returns `Some((s, o))` if both
+    /// contain values, otherwise `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// let x = some(1u8);
+    /// let y = some('h');
+    /// let z = none::<u8>();
+    ///
+    /// assert_eq!(x.zip(y), Some((1, 'h')));
+    /// assert_eq!(x.zip(z), None);
+    /// ```
+    #[inline]
+    pub fn zip<U>(self, other: Optioned<U>) -> Option<(T, U)>
+    where
+        U: Noned + Copy,
+    {
+        self.zip_with(other, |a, b| (a, b))
+    }
+
+    /// Zips this `Optioned` with another using `f`. Returns `Some(f(s, o))`
+    /// if both contain values, otherwise `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// #[derive(Debug, PartialEq)]
+    /// struct Point {
+    ///     x: f64,
+    ///     y: f64,
+    /// }
+    ///
+    /// let x = some(17.5);
+    /// let y = some(42.7);
+    ///
+    /// assert_eq!(x.zip_with(y, |x, y| Point { x, y }), Some(Point { x: 17.5, y: 42.7 }));
+    /// assert_eq!(x.zip_with(none(), |x, y| Point { x, y }), None);
+    /// ```
+    #[inline]
+    pub fn zip_with<U, R, F>(self, other: Optioned<U>, f: F) -> Option<R>
+    where
+        U: Noned + Copy,
+        F: FnOnce(T, U) -> R,
+    {
+        if self.is_some() && other.is_some() {
+            Option::Some(f(self.value, other.value))
+        } else {
+            Option::None
+        }
+    }
+
+    /// Unzips an optional pair into a pair of `Optioned`s, like
+    /// `Option<(T, U)>::unzip`.
+    ///
+    /// # Panics
+    ///
+    /// if either value of the pair is its None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none, Optioned};
+    /// let x = Some((1u8, 'h'));
+    /// let y = None::<(u8, char)>;
+    ///
+    /// assert_eq!(Optioned::unzip(x), (some(1), some('h')));
+    /// assert_eq!(Optioned::unzip(y), (none(), none()));
+    /// ```
+    #[inline]
+    pub fn unzip<U>(pair: Option<(T, U)>) -> (Optioned<T>, Optioned<U>)
+    where
+        U: Noned + Copy,
+    {
+        match pair {
+            Option::Some((t, u)) => (some(t), some(u)),
+            Option::None => (none(), none()),
+        }
+    }
+
+    /// Removes one level of nesting, like `Option<Option<T>>::flatten`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none, Optioned};
+    /// assert_eq!(some(6u32), Optioned::flatten(Some(some(6))));
+    /// assert_eq!(none(), Optioned::flatten(Some(none::<u32>())));
+    /// assert_eq!(none(), Optioned::<u32>::flatten(None));
+    /// ```
+    #[inline]
+    pub fn flatten(nested: Option<Optioned<T>>) -> Optioned<T> {
+        nested.unwrap_or_else(none)
+    }
+
+    /// Copies a referenced `Optioned`, if any, removing one level of
+    /// nesting. This is handy with methods like `slice::get`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none, Optioned};
+    /// let v = [some(1i64), none()];
+    /// assert_eq!(some(1), Optioned::copied(v.get(0)));
+    /// assert_eq!(none(), Optioned::copied(v.get(1)));
+    /// assert_eq!(none(), Optioned::copied(v.get(2)));
+    /// ```
+    #[inline]
+    pub fn copied(nested: Option<&Optioned<T>>) -> Optioned<T> {
+        nested.map_or_else(none, |&o| o)
+    }
+
+    /// Returns a guard to change the contained value in place, or `None` if
+    /// there is no value. If the None value is written through the guard,
+    /// dropping it panics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// let mut x = some(2u32);
+    /// if let Some(mut v) = x.as_mut() {
+    ///     *v *= 21;
+    /// }
+    /// assert_eq!(some(42), x);
+    /// assert!(none::<u32>().as_mut().is_none());
+    /// ```
+    ///
+    /// ```should_panic
+    ///# use optional::some;
+    /// let mut x = some(2u8);
+    /// *x.as_mut().unwrap() = 255; // panics when the guard is dropped
+    /// ```
+    #[inline]
+    pub fn as_mut(&mut self) -> Option<OptionedMut<'_, T>> {
+        self.as_mut_with(NoneWritePolicy::Panic)
+    }
+
+    /// Returns a guard to change the contained value in place, or `None` if
+    /// there is no value. The policy decides what happens if the None value
+    /// is written through the guard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none, NoneWritePolicy};
+    /// let mut x = some(1.5f32);
+    /// if let Some(mut v) = x.as_mut_with(NoneWritePolicy::MakeNone) {
+    ///     *v = (*v - 2.0).sqrt();
+    /// }
+    /// assert_eq!(none(), x);
+    /// ```
+    #[inline]
+    pub fn as_mut_with(&mut self, policy: NoneWritePolicy) -> Option<OptionedMut<'_, T>> {
+        if self.is_none() {
+            Option::None
+        } else {
+            Option::Some(OptionedMut {
+                value: &mut self.value,
+                policy,
+            })
+        }
+    }
+}
+
+impl<T: Noned + Copy + Clone + Default> Optioned<T> {
+    /// Returns the contained value or a default
+    ///
+    /// Consumes the `self` argument then, if it [`is_some`], returns the
+    /// contained value, otherwise if none, returns the default value for
+    /// that type as per the `Default` trait.
+    ///
+    /// [`is_some`]: struct.Optioned.html#method.is_some
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// assert_eq!(1u8, some(1).unwrap_or_default());
+    /// assert_eq!(0u32, none().unwrap_or_default());
+    /// ```
+    #[inline]
+    pub fn unwrap_or_default(self) -> T {
+        if self.is_some() {
+            self.value
+        } else {
+            Default::default()
+        }
+    }
+
+    /// Inserts the default value if the `Optioned` is `None`, then returns
+    /// a guard to change the contained value in place, like
+    /// [`as_mut`](#method.as_mut).
+    ///
+    /// # Panics
+    ///
+    /// if the default value is inserted and is the None value, or if the
+    /// None value is written through the guard
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none, Optioned};
+    /// let mut x: Optioned<u32> = none();
+    /// {
+    ///     let mut y = x.get_or_insert_default();
+    ///     assert_eq!(*y, 0);
+    ///     *y = 7;
+    /// }
+    /// assert_eq!(x, some(7));
+    /// ```
+    #[inline]
+    pub fn get_or_insert_default(&mut self) -> OptionedMut<'_, T> {
+        self.get_or_insert_with(Default::default)
+    }
+}
+
+impl<T: Noned + Copy + PartialEq> Optioned<T> {
+    /// Returns `true' if this `Optioned` contains the given value,
+    /// `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    /// assert!(some(42u8).contains(42));
+    /// assert!(!some(1u32).contains(12));
+    /// assert!(!none().contains(0usize));
+    /// ```
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        self.is_some() && self.value == value
+    }
+}
+
+/// Create an `Optioned<T>` that is `some(t)`.
+///
+/// # Panics
+///
 /// panics if the supplied value is the None value
 ///
 /// # Examples
@@ -2169,6 +3163,88 @@ impl<T: Noned + Copy> Default for Optioned<T> {
     }
 }
 
+/// What to do when the None value is written through an
+/// [`OptionedMut`](struct.OptionedMut.html) guard
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NoneWritePolicy {
+    /// Panic when the guard is dropped
+    Panic,
+    /// Leave the `Optioned` `None`
+    MakeNone,
+}
+
+/// A guard for changing the value of an `Optioned` in place, returned by
+/// [`Optioned::as_mut`](struct.Optioned.html#method.as_mut) and the
+/// `insert`/`get_or_insert` methods. The value is checked against the None
+/// value when the guard is dropped.
+pub struct OptionedMut<'a, T: Noned + Copy + 'a> {
+    value: &'a mut T,
+    policy: NoneWritePolicy,
+}
+
+impl<'a, T: Noned + Copy> Deref for OptionedMut<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<'a, T: Noned + Copy> DerefMut for OptionedMut<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<'a, T: Noned + Copy> Drop for OptionedMut<'a, T> {
+    fn drop(&mut self) {
+        if self.value.is_none() {
+            // panicking while unwinding would abort
+            if self.policy == NoneWritePolicy::Panic && !std::thread::panicking() {
+                panic!("the None value was written to an Optioned");
+            }
+            *self.value = T::get_none();
+        }
+    }
+}
+
+impl<'a, T: Noned + Copy + Debug> Debug for OptionedMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        self.value.fmt(f)
+    }
+}
+
+/// The error returned when trying to make an `Optioned` or `OptionBool`
+/// contain the None value. It gives back the rejected value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SentinelError<T> {
+    /// the rejected value, which is the None value
+    pub value: T,
+}
+
+impl<T> fmt::Display for SentinelError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        f.write_str("the None value cannot be used as a value")
+    }
+}
+
+impl<T: Debug> std::error::Error for SentinelError<T> {}
+
+/// The error returned when trying to get the value of an `Optioned` or
+/// `OptionBool` that is `None`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NoneError;
+
+impl fmt::Display for NoneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        f.write_str("the value is None")
+    }
+}
+
+impl std::error::Error for NoneError {}
+
 /// iterate over an Optioned<T>
 #[derive(Copy, Clone)]
 pub struct OptionedIter<T: Noned + Copy> {
@@ -2184,6 +3260,45 @@ impl<T: Noned + Copy> Iterator for OptionedIter<T> {
     }
 }
 
+/// `IntoIterator` works as expected
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+/// let mut sum = 0;
+/// for x in some(2u8) { sum += x; }
+/// for x in none::<u8>() { sum += x; }
+/// assert_eq!(2, sum);
+/// ```
+impl<T: Noned + Copy> IntoIterator for Optioned<T> {
+    type Item = T;
+    type IntoIter = OptionedIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> OptionedIter<T> {
+        self.iter()
+    }
+}
+
+/// Index for `RangeFull` (to slice)
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+/// assert_eq!(&[3u16], &some(3u16)[..]);
+/// assert!(none::<u16>()[..].is_empty());
+/// ```
+impl<T: Noned + Copy> Index<RangeFull> for Optioned<T> {
+    type Output = [T];
+
+    #[inline]
+    fn index(&self, _: RangeFull) -> &[T] {
+        self.as_slice()
+    }
+}
+
 impl<'a, T: Noned + Copy> From<&'a Option<T>> for Optioned<T> {
     #[inline]
     fn from(o: &Option<T>) -> Optioned<T> {
@@ -2218,3 +3333,40 @@ impl<T: Noned + Copy> From<T> for Optioned<T> {
         wrap(o)
     }
 }
+
+// `impl<T> TryFrom<Optioned<T>> for T` is not allowed by the orphan rules,
+// so this is implemented per type; implement it likewise for your own types.
+macro_rules! try_from_optioned {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<Optioned<$t>> for $t {
+                type Error = NoneError;
+
+                #[inline]
+                fn try_from(o: Optioned<$t>) -> Result<$t, NoneError> {
+                    o.into_result()
+                }
+            }
+        )*
+    };
+}
+
+try_from_optioned!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64, char);
+
+/// # Examples
+///
+/// ```
+///# use optional::{some, none, NoneError};
+/// use std::convert::TryFrom;
+///
+/// assert_eq!(Ok(-3i32), i32::try_from(some(-3i32)));
+/// assert_eq!(Err(NoneError), f64::try_from(none::<f64>()));
+/// ```
+impl TryFrom<OptionBool> for bool {
+    type Error = NoneError;
+
+    #[inline]
+    fn try_from(o: OptionBool) -> Result<bool, NoneError> {
+        o.ok_or(NoneError)
+    }
+}
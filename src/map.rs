@@ -0,0 +1,548 @@
+//! Hash maps and sets whose empty buckets are marked by the None value
+//!
+//! [`NonedHashMap`](struct.NonedHashMap.html) is an open-addressing hash map
+//! with linear probing. A bucket whose key is the None value of the key type
+//! is empty, so no separate control bytes are needed. In exchange, the None
+//! value itself cannot be used as a key:
+//!
+//! ```
+//!# use optional::map::NonedHashMap;
+//! let mut ages = NonedHashMap::new();
+//! assert_eq!(Ok(None), ages.insert(7u32, "seven"));
+//! assert_eq!(Some(&"seven"), ages.get(&7));
+//! assert!(ages.insert(std::u32::MAX, "max").is_err());
+//! ```
+//!
+//! Values must implement `Default`, which fills the empty buckets. Removed
+//! entries leave tombstones behind, tracked with one bit per bucket, unless
+//! the next bucket is empty; tombstones are cleared when the table is
+//! rebuilt.
+
+use super::Noned;
+use std::collections::hash_map::RandomState;
+use std::error;
+use std::fmt::{self, Debug, Display, Error};
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+
+const MIN_CAPACITY: usize = 8;
+
+/// The error returned when trying to insert the None value as a key. It
+/// gives back the rejected key and value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NoneKeyError<K, V = ()> {
+    /// the rejected key, which is the None value
+    pub key: K,
+    /// the rejected value
+    pub value: V,
+}
+
+impl<K, V> Display for NoneKeyError<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        f.write_str("the None value cannot be used as a key")
+    }
+}
+
+impl<K: Debug, V: Debug> error::Error for NoneKeyError<K, V> {}
+
+/// A hash map for keys that implement `Noned`, which uses the None value to
+/// mark empty buckets.
+///
+/// Empty buckets hold `V::default()` as their value, so no extra space is
+/// spent on marking which values are present.
+#[derive(Clone)]
+pub struct NonedHashMap<K: Noned + Hash + Eq, V, S = RandomState> {
+    keys: Vec<K>,
+    values: Vec<V>,
+    // one bit per bucket, set if the bucket holds a removed key
+    tombs: Vec<u64>,
+    len: usize,
+    tombstones: usize,
+    hasher: S,
+}
+
+impl<K: Noned + Hash + Eq, V: Default> NonedHashMap<K, V, RandomState> {
+    /// Creates an empty map. It doesn't allocate until the first insert.
+    #[inline]
+    pub fn new() -> Self {
+        NonedHashMap::with_hasher(RandomState::new())
+    }
+
+    /// Creates an empty map that can hold at least `capacity` entries
+    /// without reallocating.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        NonedHashMap::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K: Noned + Hash + Eq, V: Default, S: BuildHasher> NonedHashMap<K, V, S> {
+    /// Creates an empty map that uses the given hasher.
+    #[inline]
+    pub fn with_hasher(hasher: S) -> Self {
+        NonedHashMap {
+            keys: Vec::new(),
+            values: Vec::new(),
+            tombs: Vec::new(),
+            len: 0,
+            tombstones: 0,
+            hasher,
+        }
+    }
+
+    /// Creates an empty map that uses the given hasher and can hold at least
+    /// `capacity` entries without reallocating.
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let mut map = NonedHashMap::with_hasher(hasher);
+        if capacity > 0 {
+            map._rebuild(_buckets_for(capacity));
+        }
+        map
+    }
+
+    /// Returns the number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of entries the map can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.keys.len() * 3 / 4
+    }
+
+    /// Inserts a value, returning the value previously stored for the key,
+    /// if any.
+    ///
+    /// # Errors
+    ///
+    /// if `key` is the None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::map::NonedHashMap;
+    /// let mut map = NonedHashMap::new();
+    /// assert_eq!(Ok(None), map.insert('a', 1));
+    /// assert_eq!(Ok(Some(1)), map.insert('a', 2));
+    /// let err = map.insert('\0', 3).unwrap_err();
+    /// assert_eq!(('\0', 3), (err.key, err.value));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, NoneKeyError<K, V>> {
+        if key.is_none() {
+            return Err(NoneKeyError { key, value });
+        }
+        if (self.len + self.tombstones + 1) * 4 > self.keys.len() * 3 {
+            let buckets = if (self.len + 1) * 8 > self.keys.len() * 3 {
+                MIN_CAPACITY.max(self.keys.len() * 2)
+            } else {
+                self.keys.len() // only clear the tombstones
+            };
+            self._rebuild(buckets);
+        }
+        let mask = self.keys.len() - 1;
+        let mut i = self._home(&key);
+        let mut free = None;
+        while !self.keys[i].is_none() {
+            let tomb = self.tombstones > 0 && self._is_tomb(i);
+            if tomb {
+                free = free.or(Some(i));
+            } else if self.keys[i] == key {
+                return Ok(Some(mem::replace(&mut self.values[i], value)));
+            }
+            i = (i + 1) & mask;
+        }
+        if let Some(t) = free {
+            self.tombs[t / 64] &= !(1 << (t % 64));
+            self.tombstones -= 1;
+            i = t;
+        }
+        self.keys[i] = key;
+        self.values[i] = value;
+        self.len += 1;
+        Ok(None)
+    }
+
+    /// Returns a reference to the value stored for the key, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::map::NonedHashMap;
+    /// let mut map = NonedHashMap::new();
+    /// map.insert(1i64, "one").unwrap();
+    /// assert_eq!(Some(&"one"), map.get(&1));
+    /// assert_eq!(None, map.get(&2));
+    /// ```
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self._find(key).map(|i| &self.values[i])
+    }
+
+    /// Returns a mutable reference to the value stored for the key, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::map::NonedHashMap;
+    /// let mut map = NonedHashMap::new();
+    /// map.insert(1i64, 10).unwrap();
+    /// *map.get_mut(&1).unwrap() += 1;
+    /// assert_eq!(Some(&11), map.get(&1));
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self._find(key) {
+            Some(i) => Some(&mut self.values[i]),
+            None => None,
+        }
+    }
+
+    /// Returns `true` if the map has a value for the key.
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self._find(key).is_some()
+    }
+
+    /// Removes and returns the value stored for the key, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::map::NonedHashMap;
+    /// let mut map = NonedHashMap::new();
+    /// map.insert(1u8, "one").unwrap();
+    /// assert_eq!(Some("one"), map.remove(&1));
+    /// assert_eq!(None, map.remove(&1));
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = self._find(key)?;
+        let mask = self.keys.len() - 1;
+        if self.keys[(i + 1) & mask].is_none() {
+            // no probe sequence continues past this bucket
+            self.keys[i] = K::get_none();
+        } else {
+            self.tombs[i / 64] |= 1 << (i % 64);
+            self.tombstones += 1;
+        }
+        self.len -= 1;
+        Some(mem::take(&mut self.values[i]))
+    }
+
+    /// Removes all entries, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        for k in self.keys.iter_mut() {
+            *k = K::get_none();
+        }
+        for v in self.values.iter_mut() {
+            *v = V::default();
+        }
+        for t in self.tombs.iter_mut() {
+            *t = 0;
+        }
+        self.len = 0;
+        self.tombstones = 0;
+    }
+
+    /// Returns an iterator over all entries in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::map::NonedHashMap;
+    /// let mut map = NonedHashMap::new();
+    /// map.insert(1u16, 'a').unwrap();
+    /// map.insert(2u16, 'b').unwrap();
+    /// let mut entries: Vec<_> = map.iter().collect();
+    /// entries.sort();
+    /// assert_eq!(vec![(&1, &'a'), (&2, &'b')], entries);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> NonedHashMapIter<'_, K, V> {
+        NonedHashMapIter {
+            keys: &self.keys,
+            values: &self.values,
+            tombs: &self.tombs,
+            pos: 0,
+        }
+    }
+
+    /// Returns an iterator over all keys in arbitrary order.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { iter: self.iter() }
+    }
+
+    /// Returns an iterator over all values in arbitrary order.
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { iter: self.iter() }
+    }
+
+    #[inline]
+    fn _home(&self, key: &K) -> usize {
+        self.hasher.hash_one(key) as usize & (self.keys.len() - 1)
+    }
+
+    #[inline]
+    fn _is_tomb(&self, i: usize) -> bool {
+        _is_tomb(&self.tombs, i)
+    }
+
+    fn _find(&self, key: &K) -> Option<usize> {
+        if self.len == 0 || key.is_none() {
+            return None;
+        }
+        let mask = self.keys.len() - 1;
+        let mut i = self._home(key);
+        while !self.keys[i].is_none() {
+            if self.keys[i] == *key && !self._is_tomb(i) {
+                return Some(i);
+            }
+            i = (i + 1) & mask;
+        }
+        None
+    }
+
+    // reinserts all entries into `buckets` (a power of two) empty buckets
+    fn _rebuild(&mut self, buckets: usize) {
+        let keys = mem::replace(
+            &mut self.keys,
+            (0..buckets).map(|_| K::get_none()).collect(),
+        );
+        let values = mem::replace(
+            &mut self.values,
+            (0..buckets).map(|_| V::default()).collect(),
+        );
+        let tombs = mem::replace(&mut self.tombs, vec![0; buckets.div_ceil(64)]);
+        self.tombstones = 0;
+        let mask = buckets - 1;
+        for (j, (key, value)) in keys.into_iter().zip(values).enumerate() {
+            if key.is_none() || _is_tomb(&tombs, j) {
+                continue;
+            }
+            let mut i = self._home(&key);
+            while !self.keys[i].is_none() {
+                i = (i + 1) & mask;
+            }
+            self.keys[i] = key;
+            self.values[i] = value;
+        }
+    }
+}
+
+#[inline]
+fn _is_tomb(tombs: &[u64], i: usize) -> bool {
+    tombs[i / 64] >> (i % 64) & 1 != 0
+}
+
+// the number of buckets to hold `capacity` entries at 3/4 load
+fn _buckets_for(capacity: usize) -> usize {
+    MIN_CAPACITY.max((capacity * 4 / 3 + 1).next_power_of_two())
+}
+
+impl<K: Noned + Hash + Eq, V: Default, S: BuildHasher + Default> Default for NonedHashMap<K, V, S> {
+    #[inline]
+    fn default() -> Self {
+        NonedHashMap::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> Debug for NonedHashMap<K, V, S>
+where
+    K: Noned + Hash + Eq + Debug,
+    V: Default + Debug,
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a NonedHashMap<K, V, S>
+where
+    K: Noned + Hash + Eq,
+    V: Default,
+    S: BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = NonedHashMapIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> NonedHashMapIter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// Iterator over the entries of a `NonedHashMap`
+#[derive(Clone)]
+pub struct NonedHashMapIter<'a, K: 'a, V: 'a> {
+    keys: &'a [K],
+    values: &'a [V],
+    tombs: &'a [u64],
+    pos: usize,
+}
+
+impl<'a, K: Noned, V> Iterator for NonedHashMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        while self.pos < self.keys.len() {
+            let i = self.pos;
+            self.pos += 1;
+            if !self.keys[i].is_none() && !_is_tomb(self.tombs, i) {
+                return Some((&self.keys[i], &self.values[i]));
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the keys of a `NonedHashMap`
+#[derive(Clone)]
+pub struct Keys<'a, K: 'a, V: 'a> {
+    iter: NonedHashMapIter<'a, K, V>,
+}
+
+impl<'a, K: Noned, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a K> {
+        self.iter.next().map(|(k, _)| k)
+    }
+}
+
+/// Iterator over the values of a `NonedHashMap`
+#[derive(Clone)]
+pub struct Values<'a, K: 'a, V: 'a> {
+    iter: NonedHashMapIter<'a, K, V>,
+}
+
+impl<'a, K: Noned, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a V> {
+        self.iter.next().map(|(_, v)| v)
+    }
+}
+
+/// A hash set for values that implement `Noned`, which uses the None value
+/// to mark empty buckets.
+///
+/// # Examples
+///
+/// ```
+///# use optional::map::NonedHashSet;
+/// let mut set = NonedHashSet::new();
+/// assert_eq!(Ok(true), set.insert(3i32));
+/// assert_eq!(Ok(false), set.insert(3));
+/// assert!(set.contains(&3));
+/// assert!(set.insert(std::i32::MIN).is_err());
+/// ```
+#[derive(Clone)]
+pub struct NonedHashSet<K: Noned + Hash + Eq, S = RandomState> {
+    map: NonedHashMap<K, (), S>,
+}
+
+impl<K: Noned + Hash + Eq> NonedHashSet<K, RandomState> {
+    /// Creates an empty set. It doesn't allocate until the first insert.
+    #[inline]
+    pub fn new() -> Self {
+        NonedHashSet {
+            map: NonedHashMap::new(),
+        }
+    }
+
+    /// Creates an empty set that can hold at least `capacity` elements
+    /// without reallocating.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        NonedHashSet {
+            map: NonedHashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<K: Noned + Hash + Eq, S: BuildHasher> NonedHashSet<K, S> {
+    /// Creates an empty set that uses the given hasher.
+    #[inline]
+    pub fn with_hasher(hasher: S) -> Self {
+        NonedHashSet {
+            map: NonedHashMap::with_hasher(hasher),
+        }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Adds an element, returning `true` if it was not yet present.
+    ///
+    /// # Errors
+    ///
+    /// if `key` is the None value
+    #[inline]
+    pub fn insert(&mut self, key: K) -> Result<bool, NoneKeyError<K>> {
+        self.map.insert(key, ()).map(|old| old.is_none())
+    }
+
+    /// Returns `true` if the set contains the element.
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Removes an element, returning `true` if it was present.
+    #[inline]
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.map.remove(key).is_some()
+    }
+
+    /// Removes all elements, keeping the allocated memory.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Returns an iterator over all elements in arbitrary order.
+    #[inline]
+    pub fn iter(&self) -> Keys<'_, K, ()> {
+        self.map.keys()
+    }
+}
+
+impl<K: Noned + Hash + Eq, S: BuildHasher + Default> Default for NonedHashSet<K, S> {
+    #[inline]
+    fn default() -> Self {
+        NonedHashSet::with_hasher(S::default())
+    }
+}
+
+impl<K, S> Debug for NonedHashSet<K, S>
+where
+    K: Noned + Hash + Eq + Debug,
+    S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,215 @@
+//! Write-once cells
+//!
+//! [`OnceOptioned<T>`](struct.OnceOptioned.html) and
+//! [`OnceOptionedCell<T>`](struct.OnceOptionedCell.html) are cells that can
+//! be set only once, like `std::sync::OnceLock` and `std::cell::OnceCell`.
+//! They use the None value of `T` to mean "not yet initialized", so they
+//! are no larger than `T`:
+//!
+//! ```
+//!# use optional::once::OnceOptioned;
+//! static NEXT_ID: OnceOptioned<u32> = OnceOptioned::new();
+//!
+//! assert_eq!(7, NEXT_ID.get_or_init(|| 7));
+//! assert_eq!(7, NEXT_ID.get_or_init(|| 8));
+//! assert_eq!(4, std::mem::size_of::<OnceOptioned<u32>>());
+//! ```
+
+use super::atomic::{AtomicNoned, AtomicOptioned};
+use super::{wrap, Noned, Optioned};
+use std::cell::Cell;
+use std::fmt::{self, Debug};
+use std::sync::atomic::Ordering;
+
+/// A thread-safe cell that can be written only once, using the None value
+/// of `T` to mark it as empty.
+///
+/// Unlike `OnceLock`, `get_or_init` does not block: threads that find the
+/// cell empty at the same time may each run their initializer, but only
+/// one result is stored and all of them return it.
+pub struct OnceOptioned<T: AtomicNoned> {
+    inner: AtomicOptioned<T>,
+}
+
+impl<T: AtomicNoned> OnceOptioned<T> {
+    /// Creates an empty cell.
+    #[inline]
+    pub const fn new() -> Self {
+        OnceOptioned {
+            inner: AtomicOptioned::none(),
+        }
+    }
+
+    /// Returns the value, or `None` if the cell is empty.
+    #[inline]
+    pub fn get(&self) -> Optioned<T> {
+        self.inner.load(Ordering::Acquire)
+    }
+
+    /// Sets the value if the cell is empty, or returns `value` as an error
+    /// otherwise.
+    ///
+    /// # Panics
+    ///
+    /// if `value` is the None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::some;
+    ///# use optional::once::OnceOptioned;
+    /// let cell = OnceOptioned::new();
+    /// assert_eq!(Ok(()), cell.set(0.5f32));
+    /// assert_eq!(Err(1.0), cell.set(1.0));
+    /// assert_eq!(some(0.5), cell.get());
+    /// ```
+    #[inline]
+    pub fn set(&self, value: T) -> Result<(), T> {
+        self.inner
+            .set_if_none(value, Ordering::AcqRel)
+            .map_err(|_| value)
+    }
+
+    /// Returns the value, initializing the cell with `f()` if it is empty.
+    ///
+    /// # Panics
+    ///
+    /// if `f` returns the None value
+    pub fn get_or_init<F: FnOnce() -> T>(&self, f: F) -> T {
+        let value = self.get();
+        if value.is_some() {
+            return value.unpack();
+        }
+        let value = f();
+        match self.inner.set_if_none(value, Ordering::AcqRel) {
+            Ok(()) => value,
+            Err(current) => current,
+        }
+    }
+
+    /// Takes the value out, leaving the cell empty.
+    #[inline]
+    pub fn take(&mut self) -> Optioned<T> {
+        self.inner.take(Ordering::Relaxed)
+    }
+
+    /// Consumes the cell and returns the value, if any.
+    #[inline]
+    pub fn into_inner(self) -> Optioned<T> {
+        self.inner.into_inner()
+    }
+}
+
+impl<T: AtomicNoned> Default for OnceOptioned<T> {
+    #[inline]
+    fn default() -> Self {
+        OnceOptioned::new()
+    }
+}
+
+impl<T: AtomicNoned + Debug> Debug for OnceOptioned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+/// A cell that can be written only once, using the None value of `T` to
+/// mark it as empty. It cannot be shared between threads, but works with
+/// every `Noned` type.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::once::OnceOptionedCell;
+/// let initial = OnceOptionedCell::new();
+/// assert_eq!(none(), initial.get());
+/// assert_eq!('a', initial.get_or_init(|| 'a'));
+/// assert_eq!(Err('b'), initial.set('b'));
+/// assert_eq!(some('a'), initial.get());
+/// ```
+pub struct OnceOptionedCell<T: Noned + Copy> {
+    value: Cell<T>,
+}
+
+impl<T: Noned + Copy> OnceOptionedCell<T> {
+    /// Creates an empty cell.
+    #[inline]
+    pub fn new() -> Self {
+        OnceOptionedCell {
+            value: Cell::new(T::get_none()),
+        }
+    }
+
+    /// Returns the value, or `None` if the cell is empty.
+    #[inline]
+    pub fn get(&self) -> Optioned<T> {
+        wrap(self.value.get())
+    }
+
+    /// Sets the value if the cell is empty, or returns `value` as an error
+    /// otherwise.
+    ///
+    /// # Panics
+    ///
+    /// if `value` is the None value
+    pub fn set(&self, value: T) -> Result<(), T> {
+        assert!(!value.is_none(), "cannot set the None value");
+        if self.value.get().is_none() {
+            self.value.set(value);
+            Ok(())
+        } else {
+            Err(value)
+        }
+    }
+
+    /// Returns the value, initializing the cell with `f()` if it is empty.
+    ///
+    /// If `f` initializes the cell itself, that value is kept and returned.
+    ///
+    /// # Panics
+    ///
+    /// if `f` returns the None value
+    pub fn get_or_init<F: FnOnce() -> T>(&self, f: F) -> T {
+        if self.value.get().is_none() {
+            let value = f();
+            // `f` may have set the cell
+            let _ = self.set(value);
+        }
+        self.value.get()
+    }
+
+    /// Takes the value out, leaving the cell empty.
+    #[inline]
+    pub fn take(&mut self) -> Optioned<T> {
+        wrap(self.value.replace(T::get_none()))
+    }
+
+    /// Consumes the cell and returns the value, if any.
+    #[inline]
+    pub fn into_inner(self) -> Optioned<T> {
+        wrap(self.value.into_inner())
+    }
+}
+
+impl<T: Noned + Copy> Clone for OnceOptionedCell<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        OnceOptionedCell {
+            value: self.value.clone(),
+        }
+    }
+}
+
+impl<T: Noned + Copy> Default for OnceOptionedCell<T> {
+    #[inline]
+    fn default() -> Self {
+        OnceOptionedCell::new()
+    }
+}
+
+impl<T: Noned + Copy + Debug> Debug for OnceOptionedCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
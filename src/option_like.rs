@@ -0,0 +1,128 @@
+//! A common interface for `Option`, `Optioned` and `OptionBool`
+//!
+//! [`OptionLike<Item>`](trait.OptionLike.html) lets generic code fill,
+//! coalesce or aggregate optional values regardless of their
+//! representation:
+//!
+//! ```
+//!# use optional::{some, none, Optioned, OptionBool};
+//!# use optional::option_like::OptionLike;
+//! fn coalesce<Item, O: OptionLike<Item> + Copy>(values: &[O]) -> O {
+//!     values.iter().cloned().find(|v| v.is_some()).unwrap_or_else(O::none)
+//! }
+//!
+//! assert_eq!(Some(2), coalesce(&[None, Some(2), Some(3)]));
+//! assert_eq!(some(1.5), coalesce(&[none(), none(), some(1.5f32)]));
+//! assert_eq!(OptionBool::None, coalesce::<bool, _>(&[OptionBool::None]));
+//! ```
+
+use super::{Noned, OptionBool, Optioned};
+
+/// An optional value of type `Item`.
+pub trait OptionLike<Item>: Sized {
+    /// Returns `true` if there is a value.
+    fn is_some(&self) -> bool;
+
+    /// Returns `true` if there is no value.
+    #[inline]
+    fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// Returns a copy of the value, if any.
+    fn get(&self) -> Option<Item>;
+
+    /// Returns the empty value.
+    fn none() -> Self;
+
+    /// Wraps `value`.
+    ///
+    /// # Panics
+    ///
+    /// if `value` cannot be represented, as with `Optioned::some`
+    fn from_value(value: Item) -> Self;
+
+    /// Converts into an `Option<Item>`.
+    fn into_option(self) -> Option<Item>;
+}
+
+impl<T: Clone> OptionLike<T> for Option<T> {
+    #[inline]
+    fn is_some(&self) -> bool {
+        Option::is_some(self)
+    }
+
+    #[inline]
+    fn get(&self) -> Option<T> {
+        self.clone()
+    }
+
+    #[inline]
+    fn none() -> Self {
+        Option::None
+    }
+
+    #[inline]
+    fn from_value(value: T) -> Self {
+        Option::Some(value)
+    }
+
+    #[inline]
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+impl<T: Noned + Copy> OptionLike<T> for Optioned<T> {
+    #[inline]
+    fn is_some(&self) -> bool {
+        Optioned::is_some(self)
+    }
+
+    #[inline]
+    fn get(&self) -> Option<T> {
+        Optioned::into_option(*self)
+    }
+
+    #[inline]
+    fn none() -> Self {
+        Optioned::none()
+    }
+
+    #[inline]
+    fn from_value(value: T) -> Self {
+        Optioned::some(value)
+    }
+
+    #[inline]
+    fn into_option(self) -> Option<T> {
+        Optioned::into_option(self)
+    }
+}
+
+impl OptionLike<bool> for OptionBool {
+    #[inline]
+    fn is_some(&self) -> bool {
+        OptionBool::is_some(*self)
+    }
+
+    #[inline]
+    fn get(&self) -> Option<bool> {
+        (*self).into()
+    }
+
+    #[inline]
+    fn none() -> Self {
+        OptionBool::None
+    }
+
+    #[inline]
+    fn from_value(value: bool) -> Self {
+        OptionBool::some(value)
+    }
+
+    #[inline]
+    fn into_option(self) -> Option<bool> {
+        self.into()
+    }
+}
@@ -0,0 +1,182 @@
+//! What to do when a value collides with the None value
+//!
+//! The conversions of this crate treat a value that happens to be the None
+//! value differently: `Optioned::some`, `From<Option<T>>`, `map_t` and
+//! deserialization panic, while `From<T>` and `wrap` yield `None`. Where
+//! this matters, e.g. for validated input, the functions here let the
+//! caller choose a [`CollisionPolicy`](enum.CollisionPolicy.html) instead:
+//!
+//! ```
+//!# use optional::{Optioned, some, none};
+//!# use optional::policy::CollisionPolicy;
+//! let input = [Some(3u8), None, Some(255)];
+//! let stored: Vec<_> = input
+//!     .iter()
+//!     .map(|&v| Optioned::from_option_with_policy(v, CollisionPolicy::Saturate).unwrap())
+//!     .collect();
+//! assert_eq!(vec![some(3), none(), some(254)], stored);
+//! ```
+//!
+//! There is no crate-wide default policy, e.g. set by a cargo feature. Cargo
+//! enables a feature for the whole build once any crate asks for it, so it
+//! would change `some` and `From` for every user of this crate, and two
+//! dependencies wanting different policies could not be combined. The
+//! policy is therefore always chosen by the caller.
+
+use super::{none, wrap, Noned, Optioned, SentinelError};
+
+/// What to do with a value that is the None value of its type, where an
+/// actual value was expected.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{Optioned, some, none};
+///# use optional::policy::CollisionPolicy;
+/// assert_eq!(Ok(none()), Optioned::with_policy(255u8, CollisionPolicy::MapToNone));
+/// assert_eq!(Ok(some(254)), Optioned::with_policy(255u8, CollisionPolicy::Saturate));
+/// assert!(Optioned::with_policy(255u8, CollisionPolicy::Error).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CollisionPolicy {
+    /// panic
+    Panic,
+    /// use `None` instead
+    MapToNone,
+    /// use the nearest value that is not the None value (see
+    /// `Noned::nearest_value`), or `None` if the type has none
+    Saturate,
+    /// return a `SentinelError`
+    Error,
+}
+
+impl<T: Noned + Copy> Optioned<T> {
+    /// Create an `Optioned<T>` that is `some(t)`, using `policy` if `t` is
+    /// the None value.
+    ///
+    /// # Panics
+    ///
+    /// if `t` is the None value and `policy` is `CollisionPolicy::Panic`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{Optioned, some, none};
+    ///# use optional::policy::CollisionPolicy::*;
+    /// assert_eq!(Ok(some(3i8)), Optioned::with_policy(3i8, Panic));
+    /// assert_eq!(Ok(some(-127)), Optioned::with_policy(-128i8, Saturate));
+    /// assert_eq!(Ok(none()), Optioned::with_policy(std::f32::NAN, Saturate));
+    /// assert_eq!('\0', Optioned::with_policy('\0', Error).unwrap_err().value);
+    /// ```
+    pub fn with_policy(t: T, policy: CollisionPolicy) -> Result<Self, SentinelError<T>> {
+        if !t.is_none() {
+            return Ok(wrap(t));
+        }
+        match policy {
+            CollisionPolicy::Panic => panic!("Optioned value is the None value"),
+            CollisionPolicy::MapToNone => Ok(none()),
+            CollisionPolicy::Saturate => Ok(T::nearest_value().map_or_else(none, wrap)),
+            CollisionPolicy::Error => Err(SentinelError { value: t }),
+        }
+    }
+
+    /// Converts an `Option<T>` into an `Optioned<T>`, using `policy` if it
+    /// contains the None value.
+    ///
+    /// # Panics
+    ///
+    /// if `o` is `Some` of the None value and `policy` is
+    /// `CollisionPolicy::Panic`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{Optioned, none};
+    ///# use optional::policy::CollisionPolicy::*;
+    /// assert_eq!(Ok(none()), Optioned::<u16>::from_option_with_policy(None, Error));
+    /// assert_eq!(Ok(none()), Optioned::from_option_with_policy(Some(std::u16::MAX), MapToNone));
+    /// ```
+    #[inline]
+    pub fn from_option_with_policy(
+        o: Option<T>,
+        policy: CollisionPolicy,
+    ) -> Result<Self, SentinelError<T>> {
+        o.map_or_else(|| Ok(none()), |t| Optioned::with_policy(t, policy))
+    }
+
+    /// Maps the contained value with `f` like `map_t`, using `policy` if
+    /// `f` returns the None value.
+    ///
+    /// # Panics
+    ///
+    /// if `f` returns the None value and `policy` is
+    /// `CollisionPolicy::Panic`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::policy::CollisionPolicy::*;
+    /// let to_u8 = |x: u32| x.min(255) as u8;
+    /// assert_eq!(Ok(some(254)), some(1000u32).map_t_with_policy(Saturate, to_u8));
+    /// assert!(some(1000u32).map_t_with_policy(Error, to_u8).is_err());
+    /// assert_eq!(Ok(none()), none::<u32>().map_t_with_policy(Error, to_u8));
+    /// ```
+    #[inline]
+    pub fn map_t_with_policy<U, F>(
+        self,
+        policy: CollisionPolicy,
+        f: F,
+    ) -> Result<Optioned<U>, SentinelError<U>>
+    where
+        F: FnOnce(T) -> U,
+        U: Noned + Copy,
+    {
+        Optioned::from_option_with_policy(self.map(f), policy)
+    }
+}
+
+/// Deserializes an `Optioned<T>`, using `policy` if the input contains the
+/// None value. An error from `CollisionPolicy::Error` becomes a
+/// deserialization error.
+///
+/// To choose the policy for a field, wrap this in a function for
+/// `#[serde(deserialize_with = "...")]`.
+///
+/// # Panics
+///
+/// if the input contains the None value and `policy` is
+/// `CollisionPolicy::Panic`
+///
+/// # Examples
+///
+/// ```rust
+///# extern crate serde_json;
+///# extern crate optional;
+///# use optional::{Optioned, some};
+///# use optional::policy::{deserialize_with_policy, CollisionPolicy};
+///# fn main() {
+/// let mut input = serde_json::Deserializer::from_str("255");
+/// let parsed: Result<Optioned<u8>, _> = deserialize_with_policy(&mut input, CollisionPolicy::Error);
+/// assert!(parsed.is_err());
+///
+/// let mut input = serde_json::Deserializer::from_str("255");
+/// let parsed = deserialize_with_policy(&mut input, CollisionPolicy::Saturate);
+/// assert_eq!(some(254u8), parsed.unwrap());
+///# }
+/// ```
+#[cfg(feature = "serde")]
+pub fn deserialize_with_policy<'de, D, T>(
+    deserializer: D,
+    policy: CollisionPolicy,
+) -> Result<Optioned<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Noned + Copy + serde::Deserialize<'de>,
+{
+    use serde::de::Error;
+    use serde::Deserialize;
+
+    let o = Option::<T>::deserialize(deserializer)?;
+    Optioned::from_option_with_policy(o, policy).map_err(D::Error::custom)
+}
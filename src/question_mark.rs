@@ -0,0 +1,164 @@
+//! Early return on `None`
+//!
+//! With the `unstable` feature (nightly only), `Optioned<T>` and
+//! `OptionBool` implement `Try`, so `?` returns early on `None` from
+//! functions returning `Optioned<U>`, `OptionBool` or `Option<U>`. On
+//! stable, the [`try_optioned!`](../macro.try_optioned.html) macro does the
+//! same.
+
+use super::{none, Noned, OptionBool, Optioned};
+#[cfg(feature = "unstable")]
+use std::convert::Infallible;
+#[cfg(feature = "unstable")]
+use std::ops::{ControlFlow, FromResidual, Try};
+
+/// Types that `try_optioned!` can unwrap or return `None` as. This is an
+/// implementation detail of the macro.
+pub trait OptionalTry: Sized {
+    /// The contained value
+    type Output;
+
+    /// Returns the contained value, if any.
+    fn branch(self) -> Option<Self::Output>;
+
+    /// Returns `None`.
+    fn from_none() -> Self;
+}
+
+impl<T> OptionalTry for Option<T> {
+    type Output = T;
+
+    #[inline]
+    fn branch(self) -> Option<T> {
+        self
+    }
+
+    #[inline]
+    fn from_none() -> Self {
+        Option::None
+    }
+}
+
+impl<T: Noned + Copy> OptionalTry for Optioned<T> {
+    type Output = T;
+
+    #[inline]
+    fn branch(self) -> Option<T> {
+        self.into_option()
+    }
+
+    #[inline]
+    fn from_none() -> Self {
+        none()
+    }
+}
+
+impl OptionalTry for OptionBool {
+    type Output = bool;
+
+    #[inline]
+    fn branch(self) -> Option<bool> {
+        self.into()
+    }
+
+    #[inline]
+    fn from_none() -> Self {
+        OptionBool::None
+    }
+}
+
+/// Unwraps an `Optioned<T>`, `OptionBool` or `Option<T>`, or returns `None`
+/// from the enclosing function, which may return any of these types. This
+/// is what the `?` operator does with the `unstable` feature.
+///
+/// # Examples
+///
+/// ```
+///# #[macro_use] extern crate optional;
+///# use optional::{some, none, Optioned, OptionBool};
+/// fn add(a: Optioned<u32>, b: Optioned<u32>) -> Optioned<u32> {
+///     some(try_optioned!(a) + try_optioned!(b))
+/// }
+///
+/// fn both(a: OptionBool, b: Optioned<u8>) -> Option<(bool, u8)> {
+///     Some((try_optioned!(a), try_optioned!(b)))
+/// }
+///
+///# fn main() {
+/// assert_eq!(some(3), add(some(1), some(2)));
+/// assert_eq!(none(), add(some(1), none()));
+/// assert_eq!(None, both(OptionBool::SomeTrue, none()));
+///# }
+/// ```
+#[macro_export]
+macro_rules! try_optioned {
+    ($e:expr) => {
+        match $crate::question_mark::OptionalTry::branch($e) {
+            ::std::option::Option::Some(value) => value,
+            ::std::option::Option::None => {
+                return $crate::question_mark::OptionalTry::from_none();
+            }
+        }
+    };
+}
+
+/// `?` unwraps the value or returns `None`.
+///
+/// # Panics
+///
+/// `Try::from_output` panics if the value is the None value, like `some`.
+#[cfg(feature = "unstable")]
+impl<T: Noned + Copy> Try for Optioned<T> {
+    type Output = T;
+    type Residual = Option<Infallible>;
+
+    #[inline]
+    fn from_output(output: T) -> Self {
+        Optioned::some(output)
+    }
+
+    #[inline]
+    fn branch(self) -> ControlFlow<Option<Infallible>, T> {
+        match self.into_option() {
+            Option::Some(value) => ControlFlow::Continue(value),
+            Option::None => ControlFlow::Break(Option::None),
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: Noned + Copy> FromResidual<Option<Infallible>> for Optioned<T> {
+    #[inline]
+    fn from_residual(_: Option<Infallible>) -> Self {
+        none()
+    }
+}
+
+/// `?` unwraps the value or returns `None`.
+#[cfg(feature = "unstable")]
+impl Try for OptionBool {
+    type Output = bool;
+    type Residual = Option<Infallible>;
+
+    #[inline]
+    fn from_output(output: bool) -> Self {
+        OptionBool::some(output)
+    }
+
+    #[inline]
+    fn branch(self) -> ControlFlow<Option<Infallible>, bool> {
+        match self {
+            OptionBool::SomeTrue => ControlFlow::Continue(true),
+            OptionBool::SomeFalse => ControlFlow::Continue(false),
+            OptionBool::None => ControlFlow::Break(Option::None),
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl FromResidual<Option<Infallible>> for OptionBool {
+    #[inline]
+    fn from_residual(_: Option<Infallible>) -> Self {
+        OptionBool::None
+    }
+}
@@ -0,0 +1,199 @@
+//! Ranges with optional bounds
+//!
+//! An [`OptionedRange<T>`](struct.OptionedRange.html) is a half-open range
+//! `start..end` where either bound may be `None`, meaning the range is
+//! unbounded on that side. It implements `RangeBounds<T>`, so it can be
+//! used wherever the standard ranges are accepted:
+//!
+//! ```
+//!# use optional::{some, none};
+//!# use optional::range::OptionedRange;
+//! let price = OptionedRange::new(some(10.0f64), none());
+//! assert!(price.contains(&12.5));
+//! assert!(!price.contains(&9.99));
+//! let affordable = price.intersect(&(..20.0).into());
+//! assert_eq!(OptionedRange::from(10.0..20.0), affordable);
+//! ```
+
+use super::{none, some, Noned, OptEq, Optioned};
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::ops::{Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeTo};
+
+/// A half-open range whose bounds are optional. The start is included, the
+/// end is excluded, and a `None` bound means unbounded.
+#[derive(Copy, Clone)]
+pub struct OptionedRange<T: Noned + Copy> {
+    /// The lower bound, included if present
+    pub start: Optioned<T>,
+    /// The upper bound, excluded if present
+    pub end: Optioned<T>,
+}
+
+impl<T: Noned + Copy> OptionedRange<T> {
+    /// Creates a range from its bounds.
+    #[inline]
+    pub fn new(start: Optioned<T>, end: Optioned<T>) -> Self {
+        OptionedRange { start, end }
+    }
+
+    /// Returns `true` if neither bound is present.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.start.is_none() && self.end.is_none()
+    }
+}
+
+impl<T: Noned + Copy + PartialOrd> OptionedRange<T> {
+    /// Returns `true` if `x` is within the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::range::OptionedRange;
+    /// let r = OptionedRange::new(none(), some(5u8));
+    /// assert!(r.contains(&0));
+    /// assert!(!r.contains(&5));
+    /// ```
+    #[inline]
+    pub fn contains(&self, x: &T) -> bool {
+        (self.start.is_none() || self.start.value <= *x)
+            && (self.end.is_none() || *x < self.end.value)
+    }
+
+    /// Returns `true` if no value is within the range, i.e. both bounds
+    /// are present and the start is not below the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::range::OptionedRange;
+    /// assert!(OptionedRange::from(3..3).is_empty());
+    /// assert!(!OptionedRange::new(some(3), none()).is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start.is_some()
+            && self.end.is_some()
+            && self.start.value.partial_cmp(&self.end.value) != Some(Ordering::Less)
+    }
+
+    /// Returns the range of values within both ranges. The result may be
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::range::OptionedRange;
+    /// let a = OptionedRange::from(1u32..);
+    /// let b = OptionedRange::from(..4u32);
+    /// assert_eq!(OptionedRange::from(1..4), a.intersect(&b));
+    /// assert!(a.intersect(&(..1).into()).is_empty());
+    /// ```
+    pub fn intersect(&self, other: &Self) -> Self {
+        let start = if self.start.is_none()
+            || (other.start.is_some() && other.start.value > self.start.value)
+        {
+            other.start
+        } else {
+            self.start
+        };
+        let end = if self.end.is_none() || (other.end.is_some() && other.end.value < self.end.value)
+        {
+            other.end
+        } else {
+            self.end
+        };
+        OptionedRange { start, end }
+    }
+}
+
+impl<T: Noned + Copy> RangeBounds<T> for OptionedRange<T> {
+    #[inline]
+    fn start_bound(&self) -> Bound<&T> {
+        if self.start.is_none() {
+            Bound::Unbounded
+        } else {
+            Bound::Included(&self.start.value)
+        }
+    }
+
+    #[inline]
+    fn end_bound(&self) -> Bound<&T> {
+        if self.end.is_none() {
+            Bound::Unbounded
+        } else {
+            Bound::Excluded(&self.end.value)
+        }
+    }
+}
+
+impl<T: Noned + Copy + OptEq> PartialEq for OptionedRange<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.end == other.end
+    }
+}
+
+impl<T: Noned + Copy + OptEq> Eq for OptionedRange<T> {}
+
+impl<T: Noned + Copy> Default for OptionedRange<T> {
+    /// Returns the unbounded range.
+    #[inline]
+    fn default() -> Self {
+        OptionedRange::from(..)
+    }
+}
+
+impl<T: Noned + Copy + Debug> Debug for OptionedRange<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.start.is_some() {
+            self.start.value.fmt(f)?;
+        }
+        f.write_str("..")?;
+        if self.end.is_some() {
+            self.end.value.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// # Panics
+///
+/// if a bound is the None value
+impl<T: Noned + Copy> From<Range<T>> for OptionedRange<T> {
+    #[inline]
+    fn from(range: Range<T>) -> Self {
+        OptionedRange::new(some(range.start), some(range.end))
+    }
+}
+
+/// # Panics
+///
+/// if the start is the None value
+impl<T: Noned + Copy> From<RangeFrom<T>> for OptionedRange<T> {
+    #[inline]
+    fn from(range: RangeFrom<T>) -> Self {
+        OptionedRange::new(some(range.start), none())
+    }
+}
+
+/// # Panics
+///
+/// if the end is the None value
+impl<T: Noned + Copy> From<RangeTo<T>> for OptionedRange<T> {
+    #[inline]
+    fn from(range: RangeTo<T>) -> Self {
+        OptionedRange::new(none(), some(range.end))
+    }
+}
+
+impl<T: Noned + Copy> From<RangeFull> for OptionedRange<T> {
+    #[inline]
+    fn from(_: RangeFull) -> Self {
+        OptionedRange::new(none(), none())
+    }
+}
@@ -0,0 +1,196 @@
+//! Detecting runs of `Some` and `None` values in slices
+//!
+//! [`runs`](fn.runs.html) splits a slice of `Optioned<T>` or `OptionBool`
+//! into maximal runs of consecutive `Some` or `None` values, e.g. to report
+//! outages in a sensor stream:
+//!
+//! ```
+//!# use optional::{some, none, Optioned};
+//!# use optional::runs::{runs, Run, RunKind};
+//! let readings: Vec<Optioned<u32>> = vec![some(1), none(), none(), some(4)];
+//! let outages: Vec<Run> = runs(&readings)
+//!     .filter(|r| r.kind == RunKind::None)
+//!     .collect();
+//! assert_eq!(vec![Run { start: 1, len: 2, kind: RunKind::None }], outages);
+//! ```
+//!
+//! Long homogeneous stretches are skipped a chunk at a time with
+//! branch-free counting, which the compiler can vectorize.
+
+use super::{Noned, OptionBool, Optioned};
+
+/// Element types that can be split into runs.
+pub trait MaybeNone: Copy {
+    /// Returns `true` if this element is `None`.
+    fn is_none(&self) -> bool;
+}
+
+impl<T: Noned + Copy> MaybeNone for Optioned<T> {
+    #[inline]
+    fn is_none(&self) -> bool {
+        self.value.is_none()
+    }
+}
+
+impl MaybeNone for OptionBool {
+    #[inline]
+    fn is_none(&self) -> bool {
+        *self == OptionBool::None
+    }
+}
+
+/// Whether a [`Run`](struct.Run.html) consists of `Some` or `None` values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RunKind {
+    /// a run of `Some` values
+    Some,
+    /// a run of `None` values
+    None,
+}
+
+/// A maximal run of consecutive `Some` or `None` values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Run {
+    /// index of the first element of the run
+    pub start: usize,
+    /// number of elements in the run, always at least 1
+    pub len: usize,
+    /// whether the run is made up of `Some` or `None` values
+    pub kind: RunKind,
+}
+
+impl Run {
+    /// Returns the index one past the last element of the run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::runs::{Run, RunKind};
+    /// assert_eq!(5, Run { start: 3, len: 2, kind: RunKind::None }.end());
+    /// ```
+    #[inline]
+    pub fn end(&self) -> usize {
+        self.start + self.len
+    }
+}
+
+const CHUNK: usize = 16;
+
+// Returns the index of the first element at or after `start` that is not of
+// the given kind, or `values.len()`.
+fn _run_end<E: MaybeNone>(values: &[E], start: usize, none: bool) -> usize {
+    let full = if none { CHUNK } else { 0 };
+    let mut i = start;
+    while i + CHUNK <= values.len() {
+        let nones = values[i..i + CHUNK].iter().filter(|e| e.is_none()).count();
+        if nones != full {
+            break;
+        }
+        i += CHUNK;
+    }
+    values[i..]
+        .iter()
+        .position(|e| e.is_none() != none)
+        .map_or(values.len(), |p| i + p)
+}
+
+/// Iterator returned by [`runs`](fn.runs.html)
+#[derive(Clone)]
+pub struct Runs<'a, E: MaybeNone + 'a> {
+    values: &'a [E],
+    pos: usize,
+}
+
+impl<'a, E: MaybeNone> Iterator for Runs<'a, E> {
+    type Item = Run;
+
+    #[inline]
+    fn next(&mut self) -> Option<Run> {
+        let start = self.pos;
+        let none = self.values.get(start)?.is_none();
+        self.pos = _run_end(self.values, start, none);
+        Some(Run {
+            start,
+            len: self.pos - start,
+            kind: if none { RunKind::None } else { RunKind::Some },
+        })
+    }
+}
+
+/// Splits the slice into maximal runs of `Some` and `None` values, which
+/// alternate and together cover the whole slice.
+///
+/// # Examples
+///
+/// ```
+///# use optional::OptionBool::*;
+///# use optional::runs::{runs, Run, RunKind};
+/// let flags = [SomeTrue, SomeFalse, None, None, None, SomeTrue];
+/// assert_eq!(vec![Run { start: 0, len: 2, kind: RunKind::Some },
+///                 Run { start: 2, len: 3, kind: RunKind::None },
+///                 Run { start: 5, len: 1, kind: RunKind::Some }],
+///            runs(&flags).collect::<Vec<_>>());
+/// ```
+#[inline]
+pub fn runs<E: MaybeNone>(values: &[E]) -> Runs<'_, E> {
+    Runs { values, pos: 0 }
+}
+
+/// Iterator returned by
+/// [`none_runs_longer_than`](fn.none_runs_longer_than.html)
+#[derive(Clone)]
+pub struct NoneRunsLongerThan<'a, E: MaybeNone + 'a> {
+    runs: Runs<'a, E>,
+    min: usize,
+}
+
+impl<'a, E: MaybeNone> Iterator for NoneRunsLongerThan<'a, E> {
+    type Item = Run;
+
+    #[inline]
+    fn next(&mut self) -> Option<Run> {
+        let min = self.min;
+        self.runs
+            .by_ref()
+            .find(|r| r.kind == RunKind::None && r.len > min)
+    }
+}
+
+/// Returns an iterator over all runs of more than `n` `None` values.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none, Optioned};
+///# use optional::runs::{none_runs_longer_than, Run, RunKind};
+/// let v: Vec<Optioned<f64>> = vec![none(), some(1.0), none(), none(), some(2.0)];
+/// assert_eq!(vec![Run { start: 2, len: 2, kind: RunKind::None }],
+///            none_runs_longer_than(&v, 1).collect::<Vec<_>>());
+/// ```
+#[inline]
+pub fn none_runs_longer_than<E: MaybeNone>(values: &[E], n: usize) -> NoneRunsLongerThan<'_, E> {
+    NoneRunsLongerThan {
+        runs: runs(values),
+        min: n,
+    }
+}
+
+/// Returns the longest run of `None` values, or `None` if the slice holds no
+/// `None` at all. Of several equally long runs, the first is returned.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none, Optioned};
+///# use optional::runs::{longest_none_run, Run, RunKind};
+/// let v: Vec<Optioned<u8>> = vec![none(), some(1), none(), none(), some(2)];
+/// assert_eq!(Some(Run { start: 2, len: 2, kind: RunKind::None }),
+///            longest_none_run(&v));
+/// assert_eq!(None, longest_none_run(&[some(1u8)]));
+/// ```
+pub fn longest_none_run<E: MaybeNone>(values: &[E]) -> Option<Run> {
+    none_runs_longer_than(values, 0).fold(Option::None, |best: Option<Run>, r| match best {
+        Some(b) if b.len >= r.len => best,
+        _ => Some(r),
+    })
+}
@@ -0,0 +1,292 @@
+//! A slab of values with reusable slots
+//!
+//! [`OptionedSlab<T>`](struct.OptionedSlab.html) stores values in a `Vec<T>`
+//! and hands out a [`Key`](struct.Key.html) for each. Vacant slots hold the
+//! None value of `T`, and a free list of `Optioned<u32>` indices beside
+//! them chains the vacant slots, so removed slots are reused before the
+//! slab grows.
+//!
+//! ```
+//!# use optional::{some, none};
+//!# use optional::slab::OptionedSlab;
+//! let mut entities = OptionedSlab::new();
+//! let a = entities.insert(10u64);
+//! let b = entities.insert(20);
+//! assert_eq!(some(10), entities.remove(a));
+//! assert_eq!(none(), entities.get(a));
+//! let c = entities.insert(30); // reuses the slot of `a`
+//! assert_eq!(a, c);
+//! assert_eq!(some(20), entities.get(b));
+//! ```
+
+use super::{none, some, wrap, Noned, Optioned};
+use std::mem;
+use std::slice::Iter;
+
+/// The key of a value in an `OptionedSlab`. Keys are only meaningful for
+/// the slab that returned them.
+///
+/// `Key` implements `Noned`, so `Optioned<Key>` needs no more space than
+/// `Key` itself.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key(u32);
+
+impl Key {
+    /// Returns the slot index of this key.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Noned for Key {
+    #[inline]
+    fn is_none(&self) -> bool {
+        self.0.is_none()
+    }
+
+    #[inline]
+    fn get_none() -> Key {
+        Key(u32::get_none())
+    }
+}
+
+/// A `Vec`-backed slab whose vacant slots hold the None value of `T`.
+///
+/// Since a vacant slot must hold exactly the None value, it has no room for
+/// the link to the next vacant slot. The free list is therefore kept in a
+/// separate `Vec<Optioned<u32>>`, which costs four bytes per slot.
+#[derive(Clone, Debug)]
+pub struct OptionedSlab<T: Noned + Copy> {
+    entries: Vec<T>,
+    // for each vacant slot, the next vacant slot, if any
+    next_free: Vec<Optioned<u32>>,
+    free: Optioned<u32>,
+    len: usize,
+}
+
+impl<T: Noned + Copy> OptionedSlab<T> {
+    /// Creates an empty slab.
+    #[inline]
+    pub fn new() -> Self {
+        OptionedSlab::with_capacity(0)
+    }
+
+    /// Creates an empty slab with room for `capacity` values.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        OptionedSlab {
+            entries: Vec::with_capacity(capacity),
+            next_free: Vec::with_capacity(capacity),
+            free: none(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of values in the slab.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the slab holds no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of slots, vacant or not.
+    #[inline]
+    pub fn slots(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Stores a value in a vacant slot, or a new one if there is none, and
+    /// returns its key.
+    ///
+    /// # Panics
+    ///
+    /// if the supplied value is the None value, or the slab already has
+    /// `u32::MAX` slots
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::some;
+    ///# use optional::slab::OptionedSlab;
+    /// let mut slab = OptionedSlab::new();
+    /// let key = slab.insert('x');
+    /// assert_eq!(some('x'), slab.get(key));
+    /// ```
+    pub fn insert(&mut self, value: T) -> Key {
+        assert!(!value.is_none(), "cannot insert the None value");
+        self.len += 1;
+        if self.free.is_some() {
+            let i = self.free.unpack();
+            self.free = self.next_free[i as usize];
+            self.next_free[i as usize] = none();
+            self.entries[i as usize] = value;
+            return Key(i);
+        }
+        let i = self.entries.len() as u32;
+        assert!(!i.is_none(), "slab is full");
+        self.entries.push(value);
+        self.next_free.push(none());
+        Key(i)
+    }
+
+    /// Returns the value for the key, or `None` if its slot is vacant or
+    /// doesn't exist.
+    #[inline]
+    pub fn get(&self, key: Key) -> Optioned<T> {
+        self.entries
+            .get(key.index())
+            .map_or_else(none, |&v| wrap(v))
+    }
+
+    /// Returns `true` if there is a value for the key.
+    #[inline]
+    pub fn contains(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Replaces the value for the key, returning the old value. Does
+    /// nothing and returns `None` if the slot is vacant or doesn't exist.
+    ///
+    /// # Panics
+    ///
+    /// if the supplied value is the None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::slab::OptionedSlab;
+    /// let mut slab = OptionedSlab::new();
+    /// let key = slab.insert(1i32);
+    /// assert_eq!(some(1), slab.replace(key, 2));
+    /// assert_eq!(some(2), slab.get(key));
+    /// slab.remove(key);
+    /// assert_eq!(none(), slab.replace(key, 3));
+    /// ```
+    pub fn replace(&mut self, key: Key, value: T) -> Optioned<T> {
+        let value = some(value);
+        match self.entries.get_mut(key.index()) {
+            Some(slot) if !slot.is_none() => wrap(mem::replace(slot, value.unpack())),
+            _ => none(),
+        }
+    }
+
+    /// Removes and returns the value for the key, or `None` if its slot is
+    /// already vacant or doesn't exist.
+    pub fn remove(&mut self, key: Key) -> Optioned<T> {
+        let old = self.get(key);
+        if old.is_some() {
+            self.entries[key.index()] = T::get_none();
+            self.next_free[key.index()] = self.free;
+            self.free = some(key.0);
+            self.len -= 1;
+        }
+        old
+    }
+
+    /// Removes all values, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.next_free.clear();
+        self.free = none();
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the keys and values of all occupied slots,
+    /// in slot order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::slab::OptionedSlab;
+    /// let mut slab = OptionedSlab::new();
+    /// let a = slab.insert(1u8);
+    /// let b = slab.insert(2);
+    /// let c = slab.insert(3);
+    /// slab.remove(b);
+    /// assert_eq!(vec![(a, 1), (c, 3)], slab.iter().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> OptionedSlabIter<'_, T> {
+        OptionedSlabIter {
+            iter: self.entries.iter(),
+            index: 0,
+        }
+    }
+
+    /// Moves all values to the front, dropping the vacant slots, and returns
+    /// the new index for each old slot index (`None` for vacant slots).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::slab::OptionedSlab;
+    /// let mut slab = OptionedSlab::new();
+    /// let a = slab.insert(1u8);
+    /// let b = slab.insert(2);
+    /// let c = slab.insert(3);
+    /// slab.remove(a);
+    /// let remap = slab.compact();
+    /// assert_eq!(vec![none(), some(0), some(1)], remap);
+    /// assert_eq!(2, slab.slots());
+    /// assert_eq!(vec![2, 3], slab.iter().map(|(_, v)| v).collect::<Vec<_>>());
+    /// ```
+    pub fn compact(&mut self) -> Vec<Optioned<u32>> {
+        let mut next = 0u32;
+        let remap = self
+            .entries
+            .iter()
+            .map(|v| {
+                if v.is_none() {
+                    none()
+                } else {
+                    next += 1;
+                    some(next - 1)
+                }
+            })
+            .collect();
+        self.entries.retain(|v| !v.is_none());
+        self.next_free.truncate(self.entries.len());
+        for link in &mut self.next_free {
+            *link = none();
+        }
+        self.free = none();
+        remap
+    }
+}
+
+impl<T: Noned + Copy> Default for OptionedSlab<T> {
+    #[inline]
+    fn default() -> Self {
+        OptionedSlab::new()
+    }
+}
+
+/// Iterator over the occupied slots of an `OptionedSlab`
+#[derive(Clone)]
+pub struct OptionedSlabIter<'a, T: Noned + Copy + 'a> {
+    iter: Iter<'a, T>,
+    index: u32,
+}
+
+impl<'a, T: Noned + Copy> Iterator for OptionedSlabIter<'a, T> {
+    type Item = (Key, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<(Key, T)> {
+        for &v in self.iter.by_ref() {
+            self.index += 1;
+            if !v.is_none() {
+                return Some((Key(self.index - 1), v));
+            }
+        }
+        None
+    }
+}
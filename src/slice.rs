@@ -0,0 +1,259 @@
+//! Bulk operations on slices of `Optioned` values
+//!
+//! Column kernels often apply the same `Optioned` operation to every element
+//! of a slice. The functions in this module do this in tight loops without
+//! early exits or panics, which lets the compiler vectorize them for the
+//! primitive types:
+//!
+//! ```
+//!# use optional::{some, none};
+//!# use optional::slice::{coalesce, replace_none};
+//! let mut prices = [some(10u32), none(), none()];
+//! coalesce(&mut prices, &[some(11), some(12), none()]);
+//! replace_none(&mut prices, 0);
+//! assert_eq!([some(10), some(12), some(0)], prices);
+//! ```
+//!
+//! There are also functions to convert between `Optioned` slices and the
+//! values-plus-validity-bitmap layout of columnar formats.
+//!
+//! Unlike `Optioned::map_t`, functions that compute new values don't panic
+//! when a result is the None value; that element simply becomes `None`, as
+//! with [`wrap`](../fn.wrap.html).
+
+use super::{none, wrap, Noned, Optioned};
+
+/// Applies `f` to each contained value, leaving the `None`s alone.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::map_somes_in_place;
+/// let mut v = [some(1i16), none(), some(-4)];
+/// map_somes_in_place(&mut v, |x| x * 2);
+/// assert_eq!([some(2), none(), some(-8)], v);
+/// ```
+#[inline]
+pub fn map_somes_in_place<T, F>(values: &mut [Optioned<T>], mut f: F)
+where
+    T: Noned + Copy,
+    F: FnMut(T) -> T,
+{
+    for v in values.iter_mut() {
+        *v = if v.is_some() { wrap(f(v.value)) } else { *v };
+    }
+}
+
+/// Replaces each `None` in `dst` with the element at the same index in
+/// `src`, like calling `or` elementwise.
+///
+/// # Panics
+///
+/// if the slices differ in length
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::coalesce;
+/// let mut v = [none(), some(2.0f64), none()];
+/// coalesce(&mut v, &[some(1.0), some(0.0), none()]);
+/// assert_eq!([some(1.0), some(2.0), none()], v);
+/// ```
+#[inline]
+pub fn coalesce<T: Noned + Copy>(dst: &mut [Optioned<T>], src: &[Optioned<T>]) {
+    assert_eq!(dst.len(), src.len(), "slices differ in length");
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = if d.is_none() { *s } else { *d };
+    }
+}
+
+/// Replaces each `None` with the given value, like calling `unwrap_or`
+/// elementwise.
+///
+/// # Panics
+///
+/// if `value` is the None value
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::replace_none;
+/// let mut v = [none(), some('x')];
+/// replace_none(&mut v, '?');
+/// assert_eq!([some('?'), some('x')], v);
+/// ```
+#[inline]
+pub fn replace_none<T: Noned + Copy>(values: &mut [Optioned<T>], value: T) {
+    let value = Optioned::some(value);
+    for v in values.iter_mut() {
+        *v = if v.is_none() { value } else { *v };
+    }
+}
+
+/// Combines two slices elementwise with `f`. The result is `None` where
+/// either input is `None`.
+///
+/// # Panics
+///
+/// if the slices differ in length
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::zip_with;
+/// let a = [some(1u64), none(), some(3)];
+/// let b = [some(10u64), some(20), none()];
+/// assert_eq!(vec![some(11), none(), none()], zip_with(&a, &b, |x, y| x + y));
+/// ```
+pub fn zip_with<T, U, V, F>(a: &[Optioned<T>], b: &[Optioned<U>], mut f: F) -> Vec<Optioned<V>>
+where
+    T: Noned + Copy,
+    U: Noned + Copy,
+    V: Noned + Copy,
+    F: FnMut(T, U) -> V,
+{
+    assert_eq!(a.len(), b.len(), "slices differ in length");
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| {
+            if x.is_some() && y.is_some() {
+                wrap(f(x.value, y.value))
+            } else {
+                none()
+            }
+        })
+        .collect()
+}
+
+/// Converts a slice of `Option<T>` into a `Vec<Optioned<T>>`.
+///
+/// # Panics
+///
+/// if any `Some` contains the None value
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::from_options;
+/// assert_eq!(vec![some(1u8), none()], from_options(&[Some(1u8), None]));
+/// ```
+pub fn from_options<T: Noned + Copy>(values: &[Option<T>]) -> Vec<Optioned<T>> {
+    values.iter().map(Optioned::from).collect()
+}
+
+/// Converts a slice of `Optioned<T>` into a `Vec<Option<T>>`.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::to_options;
+/// assert_eq!(vec![Some(1u8), None], to_options(&[some(1u8), none()]));
+/// ```
+pub fn to_options<T: Noned + Copy>(values: &[Optioned<T>]) -> Vec<Option<T>> {
+    values.iter().map(|o| o.into_option()).collect()
+}
+
+/// Counts the `None`s in the slice.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::count_none;
+/// assert_eq!(2, count_none(&[none(), some(1.0f32), none()]));
+/// ```
+#[inline]
+pub fn count_none<T: Noned + Copy>(values: &[Optioned<T>]) -> usize {
+    // summing the flags instead of filtering keeps the loop branch-free
+    values.iter().map(|v| v.is_none() as usize).sum()
+}
+
+/// Returns the validity bitmap of the slice: bit `i % 8` of byte `i / 8` is
+/// set iff element `i` is not `None`. This is the least significant bit
+/// first layout used by Apache Arrow. Unused bits of the last byte are
+/// cleared.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::validity_bitmap;
+/// let v = [some(1u8), none(), some(3), some(4), none(), none(), none(), none(),
+///          some(9)];
+/// assert_eq!(vec![0b0000_1101, 0b0000_0001], validity_bitmap(&v));
+/// ```
+pub fn validity_bitmap<T: Noned + Copy>(values: &[Optioned<T>]) -> Vec<u8> {
+    values
+        .chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (i, v)| byte | (v.is_some() as u8) << i)
+        })
+        .collect()
+}
+
+/// Combines values and a validity bitmap as returned by
+/// [`validity_bitmap`](fn.validity_bitmap.html) into `Optioned`s. Elements
+/// whose bit is cleared become `None`, the others are wrapped, so a valid
+/// value that equals the None value also becomes `None`.
+///
+/// # Panics
+///
+/// if the bitmap has fewer than `values.len()` bits
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::from_bitmap;
+/// assert_eq!(vec![some(1i32), none(), some(3)],
+///            from_bitmap(&[1, 0, 3], &[0b101]));
+/// ```
+pub fn from_bitmap<T: Noned + Copy>(values: &[T], bitmap: &[u8]) -> Vec<Optioned<T>> {
+    assert!(
+        bitmap.len() * 8 >= values.len(),
+        "bitmap too short for {} values",
+        values.len()
+    );
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            if bitmap[i / 8] & 1 << (i % 8) != 0 {
+                wrap(v)
+            } else {
+                none()
+            }
+        })
+        .collect()
+}
+
+/// Splits the slice into plain values, with `placeholder` in place of each
+/// `None`, and its [`validity_bitmap`](fn.validity_bitmap.html).
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::slice::{split_validity, from_bitmap};
+/// let v = [some(2.5f64), none(), some(1.0)];
+/// let (values, bitmap) = split_validity(&v, 0.0);
+/// assert_eq!(vec![2.5, 0.0, 1.0], values);
+/// assert_eq!(vec![0b101], bitmap);
+/// assert_eq!(v.to_vec(), from_bitmap(&values, &bitmap));
+/// ```
+pub fn split_validity<T: Noned + Copy>(
+    values: &[Optioned<T>],
+    placeholder: T,
+) -> (Vec<T>, Vec<u8>) {
+    let plain = values.iter().map(|v| v.unwrap_or(placeholder)).collect();
+    (plain, validity_bitmap(values))
+}
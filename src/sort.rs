@@ -0,0 +1,339 @@
+//! Sorting and searching slices of `Optioned` values
+//!
+//! `Optioned<T>` implements `Ord` by way of `OptOrd`, which always places
+//! `None` first and is not available for floats. The functions here sort
+//! any `Optioned<T>` whose `T` is `PartialOrd` and let you choose whether the
+//! `None`s end up at the front or at the back of the slice:
+//!
+//! ```
+//!# use optional::{some, none};
+//!# use optional::sort::sort_nones_last;
+//! let mut v = [some(2.5f64), none(), some(-1.0)];
+//! sort_nones_last(&mut v);
+//! assert_eq!([some(-1.0), some(2.5), none()], v);
+//! ```
+//!
+//! For the integer types there is also a radix sort, and the search
+//! functions work on slices sorted with `None`s at either end.
+
+use super::{none, opt_cmp_partial, Noned, Optioned};
+use std::cmp::Ordering;
+use std::mem;
+use std::ops::Range;
+
+#[inline]
+fn _cmp_nones_last<T: PartialOrd + Copy + Noned>(a: &T, b: &T) -> Ordering {
+    match (a.is_none(), b.is_none()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(b).unwrap(),
+    }
+}
+
+/// Sorts the slice with all `None`s before the values, keeping the order
+/// of equal values.
+///
+/// # Panics
+///
+/// if two contained values cannot be compared
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::sort::sort_nones_first;
+/// let mut v = [some(3u8), none(), some(1)];
+/// sort_nones_first(&mut v);
+/// assert_eq!([none(), some(1), some(3)], v);
+/// ```
+pub fn sort_nones_first<T: Noned + Copy + PartialOrd>(values: &mut [Optioned<T>]) {
+    values.sort_by(|a, b| opt_cmp_partial(&a.value, &b.value))
+}
+
+/// Sorts the slice with all `None`s after the values, keeping the order
+/// of equal values.
+///
+/// # Panics
+///
+/// if two contained values cannot be compared
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::sort::sort_nones_last;
+/// let mut v = [some(3u8), none(), some(1)];
+/// sort_nones_last(&mut v);
+/// assert_eq!([some(1), some(3), none()], v);
+/// ```
+pub fn sort_nones_last<T: Noned + Copy + PartialOrd>(values: &mut [Optioned<T>]) {
+    values.sort_by(|a, b| _cmp_nones_last(&a.value, &b.value))
+}
+
+/// Sorts the slice with all `None`s before the values, possibly
+/// reordering equal values.
+///
+/// # Panics
+///
+/// if two contained values cannot be compared
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::sort::sort_unstable_nones_first;
+/// let mut v = [some(0.5f32), none(), some(-0.5)];
+/// sort_unstable_nones_first(&mut v);
+/// assert_eq!([none(), some(-0.5), some(0.5)], v);
+/// ```
+pub fn sort_unstable_nones_first<T: Noned + Copy + PartialOrd>(values: &mut [Optioned<T>]) {
+    values.sort_unstable_by(|a, b| opt_cmp_partial(&a.value, &b.value))
+}
+
+/// Sorts the slice with all `None`s after the values, possibly
+/// reordering equal values.
+///
+/// # Panics
+///
+/// if two contained values cannot be compared
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::sort::sort_unstable_nones_last;
+/// let mut v = [some(0.5f32), none(), some(-0.5)];
+/// sort_unstable_nones_last(&mut v);
+/// assert_eq!([some(-0.5), some(0.5), none()], v);
+/// ```
+pub fn sort_unstable_nones_last<T: Noned + Copy + PartialOrd>(values: &mut [Optioned<T>]) {
+    values.sort_unstable_by(|a, b| _cmp_nones_last(&a.value, &b.value))
+}
+
+/// Integer types that can be radix sorted.
+///
+/// The key must preserve the order of the values. Since the None value of
+/// every integer type is either its smallest or its largest value, radix
+/// sorting moves all `None`s to one end without looking at them separately.
+pub trait RadixKey: Noned + Copy {
+    /// Returns an unsigned key that sorts like `self`. Only the lowest
+    /// `size_of::<Self>()` bytes may be set.
+    fn radix_key(&self) -> u64;
+}
+
+impl RadixKey for u8 {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl RadixKey for u16 {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl RadixKey for u32 {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl RadixKey for u64 {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        *self
+    }
+}
+
+impl RadixKey for usize {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        *self as u64
+    }
+}
+
+// flipping the sign bit maps MIN..=MAX to 0..=unsigned MAX
+impl RadixKey for i8 {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        (*self as u8 ^ 0x80) as u64
+    }
+}
+
+impl RadixKey for i16 {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        (*self as u16 ^ 0x8000) as u64
+    }
+}
+
+impl RadixKey for i32 {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        (*self as u32 ^ 0x8000_0000) as u64
+    }
+}
+
+impl RadixKey for i64 {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        *self as u64 ^ 0x8000_0000_0000_0000
+    }
+}
+
+impl RadixKey for isize {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        (*self as usize ^ isize::MIN as usize) as u64
+    }
+}
+
+impl RadixKey for char {
+    #[inline]
+    fn radix_key(&self) -> u64 {
+        *self as u64
+    }
+}
+
+// LSD radix sort by bytes; stable, so the sentinel Nones stay together at
+// whichever end their key puts them.
+fn _radix_sort<T: RadixKey>(values: &mut [Optioned<T>]) {
+    let mut buf: Vec<Optioned<T>> = vec![none(); values.len()];
+    let mut src: &mut [Optioned<T>] = values;
+    let mut dst: &mut [Optioned<T>] = &mut buf;
+    let mut swapped = false;
+    for byte in 0..mem::size_of::<T>() {
+        let shift = 8 * byte;
+        let mut counts = [0usize; 256];
+        for v in src.iter() {
+            counts[(v.value.radix_key() >> shift) as usize & 0xff] += 1;
+        }
+        if counts.contains(&src.len()) {
+            continue; // all values share this byte
+        }
+        let mut offset = 0;
+        for c in counts.iter_mut() {
+            offset += mem::replace(c, offset);
+        }
+        for v in src.iter() {
+            let bucket = &mut counts[(v.value.radix_key() >> shift) as usize & 0xff];
+            dst[*bucket] = *v;
+            *bucket += 1;
+        }
+        mem::swap(&mut src, &mut dst);
+        swapped = !swapped;
+    }
+    if swapped {
+        dst.copy_from_slice(src);
+    }
+}
+
+/// Radix sorts the slice with all `None`s before the values. This is
+/// usually faster than comparison sorts on large slices.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::sort::radix_sort_nones_first;
+/// let mut v = [some(300u32), none(), some(7), some(70_000)];
+/// radix_sort_nones_first(&mut v);
+/// assert_eq!([none(), some(7), some(300), some(70_000)], v);
+/// ```
+pub fn radix_sort_nones_first<T: RadixKey>(values: &mut [Optioned<T>]) {
+    _radix_sort(values);
+    let nones = values.iter().rev().take_while(|v| v.is_none()).count();
+    values.rotate_right(nones);
+}
+
+/// Radix sorts the slice with all `None`s after the values. This is
+/// usually faster than comparison sorts on large slices.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::sort::radix_sort_nones_last;
+/// let mut v = [some(-3i16), none(), some(5), some(-300)];
+/// radix_sort_nones_last(&mut v);
+/// assert_eq!([some(-300), some(-3), some(5), none()], v);
+/// ```
+pub fn radix_sort_nones_last<T: RadixKey>(values: &mut [Optioned<T>]) {
+    _radix_sort(values);
+    let nones = values.iter().take_while(|v| v.is_none()).count();
+    values.rotate_left(nones);
+}
+
+/// Returns the index range of the values in a slice that has all its
+/// `None`s at the front or at the back, as left by the sort functions in
+/// this module.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::sort::some_range;
+/// assert_eq!(1..3, some_range(&[none(), some(1u8), some(2)]));
+/// assert_eq!(0..2, some_range(&[some(1u8), some(2), none()]));
+/// ```
+pub fn some_range<T: Noned + Copy>(values: &[Optioned<T>]) -> Range<usize> {
+    match values.first() {
+        Some(v) if v.is_none() => values.partition_point(Optioned::is_none)..values.len(),
+        _ => 0..values.partition_point(Optioned::is_some),
+    }
+}
+
+/// Binary searches the values of a sorted slice for `x`, ignoring the
+/// `None`s at either end.
+///
+/// Like `[T]::binary_search`, returns `Ok` with the index of a matching
+/// value, or `Err` with the index where `x` could be inserted while keeping
+/// the order. The result is unspecified if the slice is not sorted.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::sort::binary_search_some;
+/// let v = [none(), none(), some(1.0f64), some(2.0), some(4.0)];
+/// assert_eq!(Ok(3), binary_search_some(&v, &2.0));
+/// assert_eq!(Err(4), binary_search_some(&v, &3.0));
+/// assert_eq!(Err(2), binary_search_some(&v, &0.0));
+/// ```
+pub fn binary_search_some<T: Noned + Copy + PartialOrd>(
+    values: &[Optioned<T>],
+    x: &T,
+) -> Result<usize, usize> {
+    let range = some_range(values);
+    let start = range.start;
+    values[range]
+        .binary_search_by(|v| v.value.partial_cmp(x).unwrap_or(Ordering::Less))
+        .map(|i| start + i)
+        .map_err(|i| start + i)
+}
+
+/// Returns the index of the first value for which `pred` is false in a
+/// slice whose values are partitioned by `pred`, ignoring the `None`s at
+/// either end.
+///
+/// # Examples
+///
+/// ```
+///# use optional::{some, none};
+///# use optional::sort::partition_point_some;
+/// let v = [some(1u16), some(5), some(9), none()];
+/// assert_eq!(2, partition_point_some(&v, |&x| x < 7));
+/// ```
+pub fn partition_point_some<T, P>(values: &[Optioned<T>], mut pred: P) -> usize
+where
+    T: Noned + Copy,
+    P: FnMut(&T) -> bool,
+{
+    let range = some_range(values);
+    range.start + values[range].partition_point(|v| pred(&v.value))
+}
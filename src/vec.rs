@@ -0,0 +1,440 @@
+//! A growable column of optional values
+//!
+//! [`OptionedVec<T>`](struct.OptionedVec.html) stores its elements as plain
+//! `T`s, using the None value of `T` for missing entries, and keeps track of
+//! how many entries are missing.
+//!
+//! ```
+//!# use optional::{some, none};
+//!# use optional::vec::OptionedVec;
+//! let mut column = OptionedVec::new();
+//! column.push_some(3u32);
+//! column.push_none();
+//! column.push_some(5);
+//! assert_eq!(1, column.count_none());
+//! assert_eq!(some(5), column.get(2));
+//! assert_eq!(&[3, std::u32::MAX, 5], column.as_raw());
+//! ```
+
+use super::{none, wrap, Noned, Optioned};
+use std::fmt::{self, Debug, Error};
+use std::iter::FromIterator;
+use std::slice::Iter;
+
+/// A `Vec`-like column of `Optioned<T>` values, backed by a `Vec<T>`.
+#[derive(Clone)]
+pub struct OptionedVec<T: Noned + Copy> {
+    values: Vec<T>,
+    nones: usize,
+}
+
+impl<T: Noned + Copy> OptionedVec<T> {
+    /// Creates an empty `OptionedVec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::vec::OptionedVec;
+    /// assert!(OptionedVec::<i8>::new().is_empty());
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        OptionedVec {
+            values: Vec::new(),
+            nones: 0,
+        }
+    }
+
+    /// Creates an empty `OptionedVec` with space for at least `capacity`
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::vec::OptionedVec;
+    /// assert!(OptionedVec::<i8>::with_capacity(10).capacity() >= 10);
+    /// ```
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        OptionedVec {
+            values: Vec::with_capacity(capacity),
+            nones: 0,
+        }
+    }
+
+    /// Creates an `OptionedVec` from raw values, treating each None value as
+    /// a missing entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let v = OptionedVec::from_raw(vec![1.0f64, std::f64::NAN]);
+    /// assert_eq!(1, v.count_none());
+    /// ```
+    pub fn from_raw(values: Vec<T>) -> Self {
+        let nones = values.iter().filter(|v| v.is_none()).count();
+        OptionedVec { values, nones }
+    }
+
+    /// Returns the raw values, with the None value in place of each missing
+    /// entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let v: OptionedVec<u8> = vec![some(1), none()].into_iter().collect();
+    /// assert_eq!(&[1, 255], v.as_raw());
+    /// ```
+    #[inline]
+    pub fn as_raw(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Converts this into the raw values, with the None value in place of
+    /// each missing entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let v: OptionedVec<u8> = vec![some(1), none()].into_iter().collect();
+    /// assert_eq!(vec![1, 255], v.into_raw());
+    /// ```
+    #[inline]
+    pub fn into_raw(self) -> Vec<T> {
+        self.values
+    }
+
+    /// Returns the number of elements, including `None`s.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there are no elements at all.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the number of elements that can be held without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Returns the number of `None` elements. This is cached, so it takes
+    /// constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let mut v: OptionedVec<u16> = vec![some(1), none(), none()].into_iter().collect();
+    /// assert_eq!(2, v.count_none());
+    /// v.set(1, some(2));
+    /// assert_eq!(1, v.count_none());
+    /// ```
+    #[inline]
+    pub fn count_none(&self) -> usize {
+        self.nones
+    }
+
+    /// Returns the number of elements that are not `None`.
+    #[inline]
+    pub fn count_some(&self) -> usize {
+        self.values.len() - self.nones
+    }
+
+    /// Appends an element.
+    #[inline]
+    pub fn push(&mut self, value: Optioned<T>) {
+        self.nones += value.is_none() as usize;
+        self.values.push(value.value);
+    }
+
+    /// Appends a value.
+    ///
+    /// # Panics
+    ///
+    /// if the supplied value is the None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::some;
+    ///# use optional::vec::OptionedVec;
+    /// let mut v = OptionedVec::new();
+    /// v.push_some('a');
+    /// assert_eq!(some('a'), v.get(0));
+    /// ```
+    ///
+    /// ```should_panic
+    ///# use optional::vec::OptionedVec;
+    /// OptionedVec::new().push_some('\0'); // panic!s
+    /// ```
+    #[inline]
+    pub fn push_some(&mut self, value: T) {
+        self.push(Optioned::some(value))
+    }
+
+    /// Appends a `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::none;
+    ///# use optional::vec::OptionedVec;
+    /// let mut v = OptionedVec::<f32>::new();
+    /// v.push_none();
+    /// assert_eq!(none(), v.get(0));
+    /// ```
+    #[inline]
+    pub fn push_none(&mut self) {
+        self.push(none())
+    }
+
+    /// Removes the last element and returns it, or `None` if empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let mut v: OptionedVec<i64> = vec![some(1), none()].into_iter().collect();
+    /// assert_eq!(Some(none()), v.pop());
+    /// assert_eq!(Some(some(1)), v.pop());
+    /// assert_eq!(None, v.pop());
+    /// ```
+    #[inline]
+    pub fn pop(&mut self) -> Option<Optioned<T>> {
+        self.values.pop().map(|v| {
+            self.nones -= v.is_none() as usize;
+            wrap(v)
+        })
+    }
+
+    /// Returns the element at `index`, or `None` if `index` is out of
+    /// bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let v: OptionedVec<i64> = vec![some(1), none()].into_iter().collect();
+    /// assert_eq!(some(1), v.get(0));
+    /// assert_eq!(none(), v.get(1));
+    /// assert_eq!(none(), v.get(2));
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Optioned<T> {
+        self.values.get(index).map_or_else(none, |&v| wrap(v))
+    }
+
+    /// Replaces the element at `index`, returning the old one.
+    ///
+    /// # Panics
+    ///
+    /// if `index` is out of bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let mut v: OptionedVec<i64> = vec![some(1), none()].into_iter().collect();
+    /// assert_eq!(none(), v.set(1, some(2)));
+    /// assert_eq!(some(2), v.get(1));
+    /// assert_eq!(0, v.count_none());
+    /// ```
+    #[inline]
+    pub fn set(&mut self, index: usize, value: Optioned<T>) -> Optioned<T> {
+        let old = wrap(self.values[index]);
+        self.nones = self.nones - old.is_none() as usize + value.is_none() as usize;
+        self.values[index] = value.value;
+        old
+    }
+
+    /// Shortens the column to `len` elements. Does nothing if it is already
+    /// shorter.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.values.len() {
+            self.nones -= self.values[len..].iter().filter(|v| v.is_none()).count();
+            self.values.truncate(len);
+        }
+    }
+
+    /// Removes all elements.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.nones = 0;
+    }
+
+    /// Removes all `None`s, keeping the order of the remaining values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let mut v: OptionedVec<u8> = vec![none(), some(1), none(), some(2)]
+    ///     .into_iter().collect();
+    /// v.compact();
+    /// assert_eq!(&[1, 2], v.as_raw());
+    /// ```
+    pub fn compact(&mut self) {
+        if self.nones > 0 {
+            self.values.retain(|v| !v.is_none());
+            self.nones = 0;
+        }
+    }
+
+    /// Appends the contents of an iterator of `Option<T>`.
+    ///
+    /// # Panics
+    ///
+    /// if any `Some` contains the None value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let mut v = OptionedVec::new();
+    /// v.extend_from_options(vec![Some(1u32), None]);
+    /// assert_eq!(vec![some(1), none()], v.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn extend_from_options<I: IntoIterator<Item = Option<T>>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(Optioned::from))
+    }
+
+    /// Returns an iterator over all elements as `Optioned<T>`.
+    #[inline]
+    pub fn iter(&self) -> OptionedVecIter<'_, T> {
+        OptionedVecIter {
+            iter: self.values.iter(),
+        }
+    }
+
+    /// Returns an iterator over the values that are not `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    ///# use optional::{some, none};
+    ///# use optional::vec::OptionedVec;
+    /// let v: OptionedVec<i8> = vec![some(1), none(), some(3)].into_iter().collect();
+    /// assert_eq!(vec![1, 3], v.iter_somes().collect::<Vec<_>>());
+    /// ```
+    #[inline]
+    pub fn iter_somes(&self) -> IterSomes<'_, T> {
+        IterSomes {
+            iter: self.values.iter(),
+        }
+    }
+}
+
+impl<T: Noned + Copy> Default for OptionedVec<T> {
+    #[inline]
+    fn default() -> Self {
+        OptionedVec::new()
+    }
+}
+
+impl<T: Noned + Copy + Debug> Debug for OptionedVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Noned + Copy> Extend<Optioned<T>> for OptionedVec<T> {
+    fn extend<I: IntoIterator<Item = Optioned<T>>>(&mut self, iter: I) {
+        for v in iter {
+            self.push(v);
+        }
+    }
+}
+
+impl<T: Noned + Copy> FromIterator<Optioned<T>> for OptionedVec<T> {
+    fn from_iter<I: IntoIterator<Item = Optioned<T>>>(iter: I) -> Self {
+        let mut v = OptionedVec::new();
+        v.extend(iter);
+        v
+    }
+}
+
+impl<'a, T: Noned + Copy> From<&'a [Optioned<T>]> for OptionedVec<T> {
+    #[inline]
+    fn from(values: &'a [Optioned<T>]) -> Self {
+        values.iter().cloned().collect()
+    }
+}
+
+impl<T: Noned + Copy> From<OptionedVec<T>> for Vec<Optioned<T>> {
+    #[inline]
+    fn from(v: OptionedVec<T>) -> Self {
+        v.iter().collect()
+    }
+}
+
+impl<'a, T: Noned + Copy> IntoIterator for &'a OptionedVec<T> {
+    type Item = Optioned<T>;
+    type IntoIter = OptionedVecIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> OptionedVecIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Iterator over the elements of an `OptionedVec`
+#[derive(Clone)]
+pub struct OptionedVecIter<'a, T: Noned + Copy + 'a> {
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T: Noned + Copy> Iterator for OptionedVecIter<'a, T> {
+    type Item = Optioned<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Optioned<T>> {
+        self.iter.next().map(|&v| wrap(v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T: Noned + Copy> DoubleEndedIterator for OptionedVecIter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Optioned<T>> {
+        self.iter.next_back().map(|&v| wrap(v))
+    }
+}
+
+impl<'a, T: Noned + Copy> ExactSizeIterator for OptionedVecIter<'a, T> {}
+
+/// Iterator over the values of an `OptionedVec` that are not `None`
+#[derive(Clone)]
+pub struct IterSomes<'a, T: Noned + Copy + 'a> {
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T: Noned + Copy> Iterator for IterSomes<'a, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.by_ref().find(|v| !v.is_none()).cloned()
+    }
+}
@@ -0,0 +1,217 @@
+extern crate optional;
+
+use optional::atomic::{AtomicOptionBool, AtomicOptioned};
+use optional::{none, some, Optioned};
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, SeqCst};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+const THREADS: usize = 8;
+const ROUNDS: usize = 200;
+
+// runs `f(thread index)` on all threads at once, `ROUNDS` times, calling
+// `check` with the results after each round
+fn race<S, F, R, C>(shared: S, f: F, mut check: C)
+where
+    S: Fn() -> Arc<AtomicOptioned<u64>>,
+    F: Fn(&AtomicOptioned<u64>, usize) -> R + Send + Sync + 'static,
+    R: Send + 'static,
+    C: FnMut(&AtomicOptioned<u64>, Vec<R>),
+{
+    let f = Arc::new(f);
+    for _ in 0..ROUNDS {
+        let atomic = shared();
+        let barrier = Arc::new(Barrier::new(THREADS));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let (atomic, barrier, f) = (atomic.clone(), barrier.clone(), f.clone());
+                thread::spawn(move || {
+                    barrier.wait();
+                    f(&atomic, i)
+                })
+            })
+            .collect();
+        let results = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        check(&atomic, results);
+    }
+}
+
+#[test]
+fn set_if_none_has_one_winner() {
+    race(
+        || Arc::new(AtomicOptioned::default()),
+        |a, i| a.set_if_none(i as u64, AcqRel).is_ok(),
+        |a, won: Vec<bool>| {
+            assert_eq!(1, won.iter().filter(|&&w| w).count());
+            let winner = won.iter().position(|&w| w).unwrap();
+            assert_eq!(some(winner as u64), a.load(Acquire));
+        },
+    );
+}
+
+#[test]
+fn take_hands_out_value_once() {
+    race(
+        || Arc::new(AtomicOptioned::new(some(7))),
+        |a, _| a.take(AcqRel),
+        |a, taken: Vec<Optioned<u64>>| {
+            assert_eq!(1, taken.iter().filter(|t| t.is_some()).count());
+            assert!(taken.contains(&some(7)));
+            assert_eq!(none(), a.load(Acquire));
+        },
+    );
+}
+
+#[test]
+fn swap_loses_no_values() {
+    race(
+        || Arc::new(AtomicOptioned::default()),
+        |a, i| a.swap(some(i as u64), AcqRel),
+        |a, prev: Vec<Optioned<u64>>| {
+            let mut seen: Vec<_> = prev.into_iter().chain(Some(a.load(Acquire))).collect();
+            seen.sort();
+            let mut expected: Vec<_> = (0..THREADS as u64).map(some).collect();
+            expected.insert(0, none());
+            assert_eq!(expected, seen);
+        },
+    );
+}
+
+#[test]
+fn fetch_update_counts() {
+    race(
+        || Arc::new(AtomicOptioned::default()),
+        |a, _| {
+            for _ in 0..100 {
+                a.fetch_update(SeqCst, Relaxed, |x| {
+                    Some(x.map_or(some(1), |x| some(x + 1)))
+                })
+                .unwrap();
+            }
+        },
+        |a, _| assert_eq!(some(100 * THREADS as u64), a.load(SeqCst)),
+    );
+}
+
+#[test]
+fn compare_exchange_claims_and_releases() {
+    // each thread claims the slot, checks it still holds its own id and
+    // releases it; nobody can observe another's claim while holding it
+    race(
+        || Arc::new(AtomicOptioned::default()),
+        |a, i| {
+            let id = some(i as u64);
+            let mut claims = 0;
+            while claims < 20 {
+                if a.compare_exchange(none(), id, Acquire, Relaxed).is_ok() {
+                    assert_eq!(id, a.load(Relaxed));
+                    assert_eq!(Ok(id), a.compare_exchange(id, none(), SeqCst, Relaxed));
+                    claims += 1;
+                } else {
+                    thread::yield_now();
+                }
+            }
+        },
+        |a, _| assert_eq!(none(), a.load(SeqCst)),
+    );
+}
+
+#[test]
+fn option_bool_set_if_unknown_has_one_winner() {
+    for _ in 0..ROUNDS {
+        let flag = Arc::new(AtomicOptionBool::default());
+        let barrier = Arc::new(Barrier::new(THREADS));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let (flag, barrier) = (flag.clone(), barrier.clone());
+                thread::spawn(move || {
+                    barrier.wait();
+                    flag.set_if_unknown(i % 2 == 0, AcqRel)
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(1, results.iter().filter(|r| r.is_ok()).count());
+        let value = flag.load(Acquire).unwrap();
+        assert!(results
+            .iter()
+            .all(|r| r.map_or_else(|v| v == value, |_| true)));
+    }
+}
+
+#[test]
+fn option_bool_kleene_ops_combine_all_threads() {
+    use optional::OptionBool::*;
+
+    for round in 0..27 {
+        // thread t contributes the (t % 3)-th base-3 digit of the round
+        let input = |t: usize| [SomeTrue, None, SomeFalse][round / [1, 3, 9][t % 3] % 3];
+        let inputs: Vec<_> = (0..THREADS).map(input).collect();
+        let and = Arc::new(AtomicOptionBool::new(SomeTrue));
+        let or = Arc::new(AtomicOptionBool::new(SomeFalse));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|t| {
+                let (and, or, value) = (and.clone(), or.clone(), input(t));
+                thread::spawn(move || {
+                    and.fetch_and(value, SeqCst);
+                    or.fetch_or(value, SeqCst);
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        let expected_and = if inputs.contains(&SomeFalse) {
+            SomeFalse
+        } else if inputs.contains(&None) {
+            None
+        } else {
+            SomeTrue
+        };
+        let expected_or = if inputs.contains(&SomeTrue) {
+            SomeTrue
+        } else if inputs.contains(&None) {
+            None
+        } else {
+            SomeFalse
+        };
+        assert_eq!(expected_and, and.load(SeqCst));
+        assert_eq!(expected_or, or.load(SeqCst));
+    }
+}
+
+#[test]
+fn once_get_or_init_agrees_across_threads() {
+    use optional::once::OnceOptioned;
+
+    for _ in 0..ROUNDS {
+        let once = Arc::new(OnceOptioned::<f64>::new());
+        let barrier = Arc::new(Barrier::new(THREADS));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let (once, barrier) = (once.clone(), barrier.clone());
+                thread::spawn(move || {
+                    barrier.wait();
+                    once.get_or_init(|| i as f64 / 2.0)
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert!(results.iter().all(|&r| some(r) == once.get()));
+    }
+}
+
+#[test]
+fn float_none_exchanges_whatever_nan() {
+    let a = AtomicOptioned::new(Optioned::from(-std::f32::NAN));
+    assert_eq!(none(), a.load(SeqCst));
+    assert_eq!(Ok(()), a.set_if_none(1.5, SeqCst));
+    assert_eq!(
+        Ok(some(1.5)),
+        a.compare_exchange(some(1.5), Optioned::from(std::f32::NAN), SeqCst, SeqCst)
+    );
+    assert_eq!(
+        Err(-0.0),
+        a.set_if_none(-0.0, SeqCst).and(a.set_if_none(0.0, SeqCst))
+    );
+}
@@ -0,0 +1,46 @@
+extern crate optional;
+
+use optional::map::{NonedHashMap, NonedHashSet};
+use std::collections::HashMap;
+
+#[test]
+fn map_matches_std() {
+    let mut x = 0x9e37_79b9_7f4a_7c15u64;
+    let mut next = || {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        x
+    };
+    let mut ours = NonedHashMap::new();
+    let mut std = HashMap::new();
+    for round in 0..20_000 {
+        let key = (next() % 500) as u16;
+        if next() % 3 == 0 {
+            assert_eq!(std.remove(&key), ours.remove(&key));
+        } else {
+            assert_eq!(Ok(std.insert(key, round)), ours.insert(key, round));
+        }
+        assert_eq!(std.len(), ours.len());
+    }
+    for key in 0..500 {
+        assert_eq!(std.get(&key), ours.get(&key));
+    }
+    let mut entries: Vec<_> = ours.iter().map(|(&k, &v)| (k, v)).collect();
+    entries.sort();
+    let mut expected: Vec<_> = std.into_iter().collect();
+    expected.sort();
+    assert_eq!(expected, entries);
+}
+
+#[test]
+fn set_rejects_none() {
+    let mut set = NonedHashSet::with_capacity(100);
+    for i in 0..100u8 {
+        assert_eq!(Ok(true), set.insert(i));
+    }
+    assert_eq!(255, set.insert(255).unwrap_err().key);
+    assert_eq!(100, set.len());
+    assert!(set.remove(&42));
+    assert!(!set.contains(&42));
+}
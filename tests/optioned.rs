@@ -10,3 +10,360 @@ fn optioned_is_some_or_none() {
     let opt_u32_none: Optioned<u32> = Optioned::none();
     assert!(opt_u32_none.is_none());
 }
+
+#[test]
+fn fill_iter_matches_in_place() {
+    use optional::fill::*;
+    use optional::{none, some};
+
+    let values: Vec<Optioned<i32>> = vec![
+        none(),
+        some(1),
+        none(),
+        none(),
+        none(),
+        some(9),
+        none(),
+        some(3),
+        none(),
+        none(),
+    ];
+    for &limit in &[None, Some(0), Some(1), Some(2), Some(5)] {
+        let mut v = values.clone();
+        ffill(&mut v, limit);
+        assert_eq!(v, ffill_iter(values.clone(), limit).collect::<Vec<_>>());
+
+        let mut v = values.clone();
+        bfill(&mut v, limit);
+        assert_eq!(v, bfill_iter(values.clone(), limit).collect::<Vec<_>>());
+
+        let mut v = values.clone();
+        fill_with(&mut v, 0, limit);
+        assert_eq!(v, fill_with_iter(values.clone(), 0, limit).collect::<Vec<_>>());
+
+        let mut v = values.clone();
+        interpolate_linear(&mut v, limit);
+        assert_eq!(
+            v,
+            interpolate_linear_iter(values.clone(), limit).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn runs_cover_slice() {
+    use optional::runs::{runs, RunKind};
+    use optional::{none, some};
+
+    // long enough to exercise the chunked scan
+    let values: Vec<Optioned<u16>> = (0..200u16)
+        .map(|i| if i % 37 < 20 { some(i) } else { none() })
+        .collect();
+    let mut pos = 0;
+    let mut last = None;
+    for run in runs(&values) {
+        assert_eq!(pos, run.start);
+        assert!(run.len > 0);
+        assert_ne!(last, Some(run.kind));
+        for v in &values[run.start..run.end()] {
+            assert_eq!(run.kind == RunKind::None, v.is_none());
+        }
+        pos = run.end();
+        last = Some(run.kind);
+    }
+    assert_eq!(values.len(), pos);
+}
+
+#[test]
+fn radix_sort_matches_comparison_sort() {
+    use optional::sort::*;
+    use optional::wrap;
+
+    let mut x = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        x
+    };
+    let ints: Vec<Optioned<i32>> = (0..1000)
+        .map(|_| match next() % 10 {
+            0 => optional::none(),
+            r => wrap((r as i32 - 5) * (next() as i32 >> 8)),
+        })
+        .collect();
+    let longs: Vec<Optioned<u64>> = (0..1000).map(|_| wrap(next() >> (next() % 64))).collect();
+
+    let (mut a, mut b) = (ints.clone(), ints.clone());
+    sort_nones_first(&mut a);
+    radix_sort_nones_first(&mut b);
+    assert_eq!(a, b);
+    sort_nones_last(&mut a);
+    radix_sort_nones_last(&mut b);
+    assert_eq!(a, b);
+
+    let (mut a, mut b) = (longs.clone(), longs.clone());
+    sort_unstable_nones_first(&mut a);
+    radix_sort_nones_first(&mut b);
+    assert_eq!(a, b);
+    sort_unstable_nones_last(&mut a);
+    radix_sort_nones_last(&mut b);
+    assert_eq!(a, b);
+    for v in &longs {
+        if let Some(x) = v.into_option() {
+            assert_eq!(Ok(x), binary_search_some(&a, &x).map(|i| a[i].unwrap()));
+        }
+    }
+}
+
+#[test]
+fn array_matches_vec() {
+    use optional::array::OptionedArray;
+
+    let mut array = OptionedArray::<u8, 8>::new();
+    let mut vec = Vec::new();
+    for i in 0..40u8 {
+        match i % 5 {
+            0 | 1 if !array.is_full() => {
+                array.push(i);
+                vec.push(i);
+            }
+            2 if !array.is_full() => {
+                let index = i as usize % (vec.len() + 1);
+                array.insert(index, i);
+                vec.insert(index, i);
+            }
+            3 if !vec.is_empty() => {
+                let index = i as usize % vec.len();
+                assert_eq!(vec.remove(index), array.remove(index));
+            }
+            _ => assert_eq!(vec.pop(), array.pop().into_option()),
+        }
+        assert_eq!(&vec[..], array.as_slice());
+        assert_eq!(vec.len() == 8, array.is_full());
+    }
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn question_mark_returns_none() {
+    use optional::{none, some, OptionBool};
+
+    fn add(a: Optioned<i16>, b: Optioned<i16>) -> Optioned<i16> {
+        some(a? + b?)
+    }
+    fn pair(a: OptionBool, b: Optioned<u8>) -> Option<(bool, u8)> {
+        Some((a?, b?))
+    }
+    fn not(a: OptionBool) -> OptionBool {
+        OptionBool::some(!a?)
+    }
+
+    assert_eq!(some(3), add(some(1), some(2)));
+    assert_eq!(none(), add(none(), some(2)));
+    assert_eq!(Some((true, 1)), pair(OptionBool::SomeTrue, some(1)));
+    assert_eq!(None, pair(OptionBool::None, some(1)));
+    assert_eq!(OptionBool::SomeFalse, not(OptionBool::SomeTrue));
+    assert_eq!(OptionBool::None, not(OptionBool::None));
+}
+
+#[test]
+fn display_parses_back() {
+    use optional::{none, some};
+
+    let values = [some(-1.25f64), none(), some(0.0), some(f64::INFINITY)];
+    for marker in &["", "NULL", "NA", "-"] {
+        let line: Vec<_> = values
+            .iter()
+            .map(|v| format!("{:6.2}", v.display_none_as(marker)))
+            .collect();
+        let parsed: Vec<_> = line
+            .iter()
+            .map(|s| Optioned::<f64>::parse_none_as(s.trim(), marker).unwrap())
+            .collect();
+        assert_eq!(&values[..], &parsed[..]);
+        assert!(line.iter().all(|s| s.len() == 6));
+    }
+    assert_eq!("  x|   ", format!("{:>3}|{:3}", some('x'), none::<char>()));
+
+    // the None value itself is rejected rather than parsed as `None`
+    assert!("NaN".parse::<Optioned<f32>>().is_err());
+    assert!("-128".parse::<Optioned<i8>>().is_err());
+    assert_eq!(Ok(some(-127)), "-127".parse::<Optioned<i8>>());
+    assert!("x".parse::<Optioned<i8>>().is_err());
+}
+
+#[test]
+fn noned_is_enough_for_keys_and_sorting() {
+    use optional::{none, some, Noned};
+    use std::collections::HashMap;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Id(u16);
+
+    impl Noned for Id {
+        fn is_none(&self) -> bool {
+            self.0 == 0
+        }
+
+        fn get_none() -> Id {
+            Id(0)
+        }
+    }
+
+    let mut ids = vec![some(Id(3)), none(), some(Id(1)), none()];
+    ids.sort();
+    assert_eq!(vec![none(), none(), some(Id(1)), some(Id(3))], ids);
+
+    let mut counts = HashMap::new();
+    for id in ids {
+        *counts.entry(id).or_insert(0) += 1;
+    }
+    assert_eq!(Some(&2), counts.get(&none()));
+    assert_eq!(Some(&1), counts.get(&some(Id(3))));
+}
+
+#[test]
+fn collision_policies() {
+    use optional::policy::CollisionPolicy::{self, *};
+    use optional::{none, some, SentinelError};
+    use std::panic::catch_unwind;
+
+    fn expected<T: optional::Noned + Copy>(
+        policy: CollisionPolicy,
+        none_value: T,
+        saturated: T,
+    ) -> Result<Optioned<T>, SentinelError<T>> {
+        match policy {
+            MapToNone => Ok(none()),
+            Saturate => Ok(some(saturated)),
+            _ => Err(SentinelError { value: none_value }),
+        }
+    }
+
+    fn convert<T: optional::Noned + Copy>(
+        t: T,
+        policy: CollisionPolicy,
+    ) -> [Result<Optioned<T>, SentinelError<T>>; 3] {
+        [
+            Optioned::with_policy(t, policy),
+            Optioned::from_option_with_policy(Some(t), policy),
+            some(0u8).map_t_with_policy(policy, |_| t),
+        ]
+    }
+
+    for &policy in &[Panic, MapToNone, Saturate, Error] {
+        // values that aren't the None value pass through unchanged
+        for r in &convert(7i32, policy) {
+            assert_eq!(Ok(some(7)), *r);
+        }
+        assert_eq!(
+            Ok(none()),
+            Optioned::<i32>::from_option_with_policy(None, policy)
+        );
+        assert_eq!(Ok(none()), none::<u8>().map_t_with_policy(policy, |_| 0i32));
+
+        if policy == Panic {
+            assert!(catch_unwind(|| Optioned::with_policy(i32::MIN, Panic)).is_err());
+            assert!(catch_unwind(|| some(1u8).map_t_with_policy(Panic, |_| '\0')).is_err());
+            continue;
+        }
+        for r in &convert(i32::MIN, policy) {
+            assert_eq!(expected(policy, i32::MIN, i32::MIN + 1), *r);
+        }
+        for r in &convert(u64::MAX, policy) {
+            assert_eq!(expected(policy, u64::MAX, u64::MAX - 1), *r);
+        }
+        for r in &convert('\0', policy) {
+            assert_eq!(expected(policy, '\0', '\u{1}'), *r);
+        }
+        // floats have no nearest value
+        for r in &convert(f64::NAN, policy) {
+            assert_eq!(policy == Error, r.is_err());
+            assert!(r.map_or(true, |o| o.is_none()));
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn collision_policies_deserialize() {
+    extern crate serde_json;
+
+    use optional::policy::deserialize_with_policy;
+    use optional::policy::CollisionPolicy::*;
+    use optional::{none, some};
+
+    let parse = |s, policy| {
+        deserialize_with_policy::<_, i8>(&mut serde_json::Deserializer::from_str(s), policy)
+    };
+    for &policy in &[Panic, MapToNone, Saturate, Error] {
+        assert_eq!(some(5), parse("5", policy).unwrap());
+        assert_eq!(none(), parse("null", policy).unwrap());
+    }
+    assert_eq!(none(), parse("-128", MapToNone).unwrap());
+    assert_eq!(some(-127), parse("-128", Saturate).unwrap());
+    assert!(parse("-128", Error).is_err());
+}
+
+#[test]
+fn option_like_agrees_across_representations() {
+    use optional::option_like::OptionLike;
+    use optional::{none, some, wrap, Noned, OptionBool};
+
+    fn fill_forward<Item, O: OptionLike<Item> + Copy>(values: &mut [O]) {
+        let mut last = O::none();
+        for v in values.iter_mut() {
+            if v.is_some() {
+                last = *v;
+            } else {
+                *v = last;
+            }
+        }
+    }
+
+    fn count_some<Item, O: OptionLike<Item>>(values: &[O]) -> usize {
+        values.iter().filter(|v| v.is_some()).count()
+    }
+
+    fn to_options<Item, O: OptionLike<Item> + Copy>(values: &[O]) -> Vec<Option<Item>> {
+        values.iter().map(|v| v.into_option()).collect()
+    }
+
+    let ints = [None, Some(3), None, Some(-1), None];
+    let mut options = ints;
+    let mut optioneds: Vec<Optioned<i32>> = ints.iter().map(|&v| v.into()).collect();
+    fill_forward(&mut options);
+    fill_forward(&mut optioneds);
+    assert_eq!(vec![None, Some(3), Some(3), Some(-1), Some(-1)], options);
+    assert_eq!(to_options(&options), to_options(&optioneds));
+    assert_eq!(count_some(&options), count_some(&optioneds));
+
+    let flags = [None, Some(true), None, Some(false), None];
+    let mut options = flags;
+    let mut bools: Vec<OptionBool> = flags.iter().map(|&v| v.into()).collect();
+    fill_forward(&mut options);
+    fill_forward(&mut bools);
+    assert_eq!(
+        vec![None, Some(true), Some(true), Some(false), Some(false)],
+        options
+    );
+    assert_eq!(to_options(&options), to_options(&bools));
+    assert_eq!(count_some(&options), count_some(&bools));
+
+    fn first_none<T: Noned + Copy>(values: &[Optioned<T>]) -> Option<usize> {
+        values.iter().position(|v| v.is_none())
+    }
+
+    // `OptionBool::None` is the None value, so `Optioned<OptionBool>` has a
+    // single empty state, as generic code bounded on `Noned` expects
+    let nested = [some(OptionBool::SomeTrue), wrap(OptionBool::None), none()];
+    assert!(OptionBool::None.is_none());
+    assert_eq!(OptionBool::None, OptionBool::get_none());
+    assert_eq!(Some(1), first_none(&nested));
+    assert_eq!(nested[1], nested[2]);
+    assert_eq!(
+        vec![Some(OptionBool::SomeTrue), None, None],
+        to_options(&nested)
+    );
+}